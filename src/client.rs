@@ -0,0 +1,152 @@
+use crate::timebase;
+use chrono::{DateTime, Local};
+use std::{cmp::Reverse, time::Duration};
+
+// --- Clients ---
+
+pub struct Client {
+    pub ip: String,
+    pub first_seen: DateTime<Local>,
+    pub last_seen: DateTime<Local>,
+    pub http_requests: u64,
+    pub udp_messages: u64,
+    pub last_path: Option<String>,
+}
+
+impl Client {
+    pub fn new(ip: String) -> Self {
+        let now = timebase::local_now();
+        Self {
+            ip,
+            first_seen: now,
+            last_seen: now,
+            http_requests: 0,
+            udp_messages: 0,
+            last_path: None,
+        }
+    }
+
+    pub fn record_http(&mut self, path: Option<String>) {
+        self.last_seen = timebase::local_now();
+        self.http_requests += 1;
+        if path.is_some() {
+            self.last_path = path;
+        }
+    }
+
+    pub fn record_udp(&mut self) {
+        self.last_seen = timebase::local_now();
+        self.udp_messages += 1;
+    }
+
+    pub fn total(&self) -> u64 {
+        self.http_requests + self.udp_messages
+    }
+
+    pub fn age(&self) -> Duration {
+        (timebase::local_now() - self.last_seen).to_std().unwrap_or_default()
+    }
+
+    pub fn is_active(&self, timeout: Duration) -> bool {
+        self.age() < timeout
+    }
+}
+
+// --- Sorting ---
+
+#[derive(Clone, Copy)]
+pub enum ClientSort {
+    LastSeen,
+    Requests,
+    Address,
+}
+
+impl ClientSort {
+    pub fn next(self) -> Self {
+        match self {
+            ClientSort::LastSeen => ClientSort::Requests,
+            ClientSort::Requests => ClientSort::Address,
+            ClientSort::Address => ClientSort::LastSeen,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ClientSort::LastSeen => "last seen",
+            ClientSort::Requests => "requests",
+            ClientSort::Address => "address",
+        }
+    }
+
+    pub fn sort(&self, clients: &mut [&Client]) {
+        match self {
+            ClientSort::LastSeen => clients.sort_by_key(|c| Reverse(c.last_seen)),
+            ClientSort::Requests => clients.sort_by_key(|c| Reverse(c.total())),
+            ClientSort::Address => clients.sort_by_key(|c| {
+                c.ip.parse::<std::net::IpAddr>().map_err(|_| c.ip.clone())
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    // Seen `secs_ago` before a fixed point, with `requests` HTTP requests
+    fn client(ip: &str, secs_ago: i64, requests: u64) -> Client {
+        let mut client = Client::new(ip.to_string());
+        client.last_seen = client.first_seen - TimeDelta::seconds(secs_ago);
+        client.http_requests = requests;
+        client
+    }
+
+    fn sorted(sort: ClientSort, clients: &[Client]) -> Vec<&str> {
+        let mut list: Vec<&Client> = clients.iter().collect();
+        sort.sort(&mut list);
+        list.iter().map(|c| c.ip.as_str()).collect()
+    }
+
+    #[test]
+    fn counters_add_up() {
+        let mut client = Client::new("10.0.0.5".to_string());
+        client.record_http(Some("/status".to_string()));
+        client.record_http(None);
+        client.record_udp();
+        assert_eq!((client.http_requests, client.udp_messages, client.total()), (2, 1, 3));
+        assert_eq!(client.last_path.as_deref(), Some("/status"));
+    }
+
+    #[test]
+    fn sorts_by_last_seen_and_requests() {
+        let clients = [client("10.0.0.1", 30, 5), client("10.0.0.2", 5, 1), client("10.0.0.3", 90, 12)];
+        assert_eq!(sorted(ClientSort::LastSeen, &clients), ["10.0.0.2", "10.0.0.1", "10.0.0.3"]);
+        assert_eq!(sorted(ClientSort::Requests, &clients), ["10.0.0.3", "10.0.0.1", "10.0.0.2"]);
+    }
+
+    #[test]
+    fn addresses_sort_numerically() {
+        let clients = [
+            client("worker-1", 0, 0),
+            client("fe80::1", 0, 0),
+            client("10.0.0.10", 0, 0),
+            client("10.0.0.2", 0, 0),
+            client("9.1.1.1", 0, 0),
+        ];
+        // IPv4 before IPv6, names that are no address last
+        assert_eq!(
+            sorted(ClientSort::Address, &clients),
+            ["9.1.1.1", "10.0.0.2", "10.0.0.10", "fe80::1", "worker-1"]
+        );
+    }
+
+    #[test]
+    fn sort_modes_cycle() {
+        let labels: Vec<&str> = std::iter::successors(Some(ClientSort::LastSeen), |s| Some(s.next()))
+            .take(4)
+            .map(|s| s.label())
+            .collect();
+        assert_eq!(labels, ["last seen", "requests", "address", "last seen"]);
+    }
+}
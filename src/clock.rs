@@ -0,0 +1,208 @@
+use crate::{config::GameConfig, timebase};
+use ratatui::style::Color;
+use std::time::{Duration, Instant};
+
+// --- Game Clock ---
+
+enum ClockState {
+    Idle,
+    Running { since: Instant, before: Duration },
+    Paused { elapsed: Duration },
+}
+
+pub struct GameClock {
+    config: GameConfig,
+    state: ClockState,
+}
+
+impl GameClock {
+    pub fn new(config: GameConfig) -> Self {
+        Self { config, state: ClockState::Idle }
+    }
+
+    pub fn start(&mut self) {
+        if let ClockState::Idle = self.state {
+            self.state = ClockState::Running { since: timebase::now(), before: Duration::ZERO };
+        }
+    }
+
+    pub fn pause(&mut self) {
+        if let ClockState::Running { .. } = self.state {
+            self.state = ClockState::Paused { elapsed: self.elapsed() };
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if let ClockState::Paused { elapsed } = self.state {
+            self.state = ClockState::Running { since: timebase::now(), before: elapsed };
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.state = ClockState::Idle;
+    }
+
+    pub fn is_idle(&self) -> bool {
+        matches!(self.state, ClockState::Idle)
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(self.state, ClockState::Running { .. })
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        match self.state {
+            ClockState::Idle => Duration::ZERO,
+            ClockState::Running { since, before } => before + timebase::now().saturating_duration_since(since),
+            ClockState::Paused { elapsed } => elapsed,
+        }
+    }
+
+    // Seconds left in the game, negative once the team is in overtime
+    pub fn remaining_secs(&self) -> i64 {
+        self.config.duration_mins as i64 * 60 - self.elapsed().as_secs() as i64
+    }
+
+    pub fn state_label(&self) -> &'static str {
+        match self.state {
+            ClockState::Idle => "READY",
+            ClockState::Running { .. } if self.remaining_secs() < 0 => "OVERTIME",
+            ClockState::Running { .. } => "RUNNING",
+            ClockState::Paused { .. } => "PAUSED",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        let remaining = self.remaining_secs();
+        if self.is_idle() {
+            Color::Gray
+        } else if remaining < 0 || remaining <= self.config.critical_mins as i64 * 60 {
+            Color::Red
+        } else if remaining <= self.config.warning_mins as i64 * 60 {
+            Color::Yellow
+        } else {
+            Color::Green
+        }
+    }
+
+    // "MM:SS" countdown, "+MM:SS" in overtime
+    pub fn display(&self) -> String {
+        let remaining = self.remaining_secs();
+        let secs = remaining.unsigned_abs();
+        let sign = if remaining < 0 { "+" } else { "" };
+        format!("{}{:02}:{:02}", sign, secs / 60, secs % 60)
+    }
+}
+
+// Time into the game as "MM:SS", minutes keep counting past the hour
+pub fn format_game_time(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+// --- Big Digits ---
+
+// Three rows high, built from half blocks so it fits a five line header
+pub fn big_text(text: &str) -> [String; 3] {
+    let mut rows = [String::new(), String::new(), String::new()];
+    for c in text.chars() {
+        let glyph: [&str; 3] = match c {
+            '0' => ["█▀█", "█ █", "▀▀▀"],
+            '1' => [" █ ", " █ ", " ▀ "],
+            '2' => ["▀▀█", "█▀▀", "▀▀▀"],
+            '3' => ["▀▀█", " ▀█", "▀▀▀"],
+            '4' => ["█ █", "▀▀█", "  ▀"],
+            '5' => ["█▀▀", "▀▀█", "▀▀▀"],
+            '6' => ["█▀▀", "█▀█", "▀▀▀"],
+            '7' => ["▀▀█", "  █", "  ▀"],
+            '8' => ["█▀█", "█▀█", "▀▀▀"],
+            '9' => ["█▀█", "▀▀█", "▀▀▀"],
+            ':' => [" ", "▀", "▀"],
+            '+' => ["   ", "▄█▄", " ▀ "],
+            _ => [" ", " ", " "],
+        };
+        for (row, part) in rows.iter_mut().zip(glyph) {
+            row.push_str(part);
+            row.push(' ');
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(duration_mins: u64) -> GameClock {
+        GameClock::new(GameConfig { duration_mins, warning_mins: 10, critical_mins: 5 })
+    }
+
+    // A clock that has been running for `secs`
+    fn running(duration_mins: u64, secs: u64) -> GameClock {
+        let mut clock = clock(duration_mins);
+        clock.state = ClockState::Running { since: timebase::now(), before: Duration::from_secs(secs) };
+        clock
+    }
+
+    #[test]
+    fn pause_and_resume_keep_the_elapsed_time() {
+        let _time = timebase::test_lock();
+        let mut clock = clock(60);
+        assert!(clock.is_idle());
+        assert_eq!(clock.state_label(), "READY");
+        clock.start();
+        assert!(clock.is_running());
+
+        let mut clock = running(60, 754);
+        clock.pause();
+        assert_eq!(clock.state_label(), "PAUSED");
+        assert_eq!(clock.elapsed().as_secs(), 754);
+        assert_eq!(clock.display(), "47:26");
+
+        // Starting again does nothing while paused, resuming goes on from the same time
+        clock.start();
+        assert_eq!(clock.state_label(), "PAUSED");
+        clock.resume();
+        assert!(clock.is_running());
+        assert_eq!(clock.elapsed().as_secs(), 754);
+
+        clock.reset();
+        assert!(clock.is_idle());
+        assert_eq!(clock.elapsed(), Duration::ZERO);
+        assert_eq!(clock.display(), "60:00");
+    }
+
+    #[test]
+    fn overtime_counts_up_with_a_plus() {
+        let _time = timebase::test_lock();
+        let clock = running(60, 3600);
+        assert_eq!(clock.display(), "00:00");
+        assert_eq!(clock.state_label(), "RUNNING");
+
+        let clock = running(60, 3725);
+        assert_eq!(clock.remaining_secs(), -125);
+        assert_eq!(clock.display(), "+02:05");
+        assert_eq!(clock.state_label(), "OVERTIME");
+
+        let clock = running(60, 3600 + 75 * 60);
+        assert_eq!(clock.display(), "+75:00");
+    }
+
+    #[test]
+    fn long_games_show_minutes_past_the_hour() {
+        let _time = timebase::test_lock();
+        assert_eq!(clock(90).display(), "90:00");
+        assert_eq!(running(120, 30).display(), "119:30");
+    }
+
+    #[test]
+    fn colour_follows_the_thresholds() {
+        let _time = timebase::test_lock();
+        assert_eq!(clock(60).color(), Color::Gray);
+        assert_eq!(running(60, 3600 - 601).color(), Color::Green);
+        assert_eq!(running(60, 3600 - 600).color(), Color::Yellow);
+        assert_eq!(running(60, 3600 - 301).color(), Color::Yellow);
+        assert_eq!(running(60, 3600 - 300).color(), Color::Red);
+        assert_eq!(running(60, 3601).color(), Color::Red);
+    }
+}
@@ -0,0 +1,802 @@
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const DEFAULT_CONFIG_FILE: &str = "servertui.toml";
+
+const USAGE: &str = "\
+Usage: servertui [OPTIONS] [-- PROGRAM [ARGS...]]
+
+Options:
+  -c, --config <FILE>    Config file (default: ./servertui.toml if present)
+      --program <PROG>   Program to run as the game server
+      --arg <ARG>        Argument for the program (repeatable, replaces config args)
+      --cwd <DIR>        Working directory for the game server
+      --env <KEY=VALUE>  Extra environment variable (repeatable)
+      --replay <FILE>    Replay a recorded session instead of running the server
+  -h, --help             Print this help
+
+Everything after `--` replaces program and arguments, e.g.
+  servertui -- ./venv/bin/python -u staging_server.py";
+
+// --- Config File ---
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub supervisor: SupervisorConfig,
+    pub game: GameConfig,
+    pub puzzles: PuzzleConfig,
+    pub probe: ProbeConfig,
+    pub clients: ClientConfig,
+    pub process: ProcessConfig,
+    pub metrics: MetricsConfig,
+    pub rules: RulesConfig,
+    pub logs: LogsConfig,
+    pub recording: RecordingConfig,
+    pub hints: HintsConfig,
+    pub history: HistoryConfig,
+    pub ui: UiConfig,
+    // Set by --replay only
+    #[serde(skip)]
+    pub replay: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: Option<PathBuf>,
+    pub env: HashMap<String, String>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        // IMPORTANT: "-u" forces unbuffered output so we see logs immediately
+        Self {
+            program: "python3".to_string(),
+            args: vec!["-u".to_string(), "server.py".to_string()],
+            cwd: None,
+            env: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SupervisorConfig {
+    pub auto_restart: bool,
+    pub backoff_initial_secs: u64,
+    pub backoff_max_secs: u64,
+    pub max_restarts: u32,
+    pub restart_window_secs: u64,
+    pub stop_grace_secs: u64,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            auto_restart: false,
+            backoff_initial_secs: 1,
+            backoff_max_secs: 60,
+            max_restarts: 5,
+            restart_window_secs: 600,
+            stop_grace_secs: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameConfig {
+    pub duration_mins: u64,
+    pub warning_mins: u64,
+    pub critical_mins: u64,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            duration_mins: 60,
+            warning_mins: 10,
+            critical_mins: 5,
+        }
+    }
+}
+
+// A puzzle that is not mentioned in the logs for `stale_secs` / `offline_secs` is flagged
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PuzzleConfig {
+    pub stale_secs: u64,
+    pub offline_secs: u64,
+    // Registration attributes shown as extra columns in the puzzle list
+    pub columns: Vec<String>,
+}
+
+impl Default for PuzzleConfig {
+    fn default() -> Self {
+        Self {
+            stale_secs: 30,
+            offline_secs: 120,
+            columns: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProbeMode {
+    Tcp,
+    Udp,
+    Http,
+}
+
+// Active health checks against every registered puzzle IP
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProbeConfig {
+    pub enabled: bool,
+    pub mode: ProbeMode,
+    pub port: u16,
+    pub path: String,
+    pub payload: String,
+    pub interval_secs: u64,
+    pub timeout_ms: u64,
+    pub history: usize,
+}
+
+impl Default for ProbeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: ProbeMode::Tcp,
+            port: 80,
+            path: "/".to_string(),
+            payload: "ping".to_string(),
+            interval_secs: 5,
+            timeout_ms: 1000,
+            history: 60,
+        }
+    }
+}
+
+// Resource usage of the server process and its children, shown in the header
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProcessConfig {
+    pub interval_ms: u64,
+    // Values at or above these are shown in red, 0 turns a warning off
+    pub warn_cpu_percent: f32,
+    pub warn_rss_mb: u64,
+    pub warn_threads: u64,
+    pub warn_open_files: u64,
+}
+
+impl Default for ProcessConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: 1000,
+            warn_cpu_percent: 80.0,
+            warn_rss_mb: 512,
+            warn_threads: 100,
+            warn_open_files: 512,
+        }
+    }
+}
+
+// Rolling history of system and server load for the system view
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MetricsConfig {
+    pub history_mins: u64,
+    pub sample_secs: u64,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self { history_mins: 15, sample_secs: 1 }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClientConfig {
+    // Clients without a request for this long move to the "recent" section
+    pub timeout_secs: u64,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self { timeout_secs: 60 }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RulesConfig {
+    // Rule file, the built-in rules are used without one
+    pub file: Option<PathBuf>,
+    pub reload: bool,
+    // Lines that are JSON objects are mapped directly instead of going through the rules
+    pub json: bool,
+}
+
+impl Default for RulesConfig {
+    fn default() -> Self {
+        Self {
+            file: None,
+            reload: true,
+            json: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogsConfig {
+    // Lines kept in memory, older ones are moved to a temporary file
+    pub memory_lines: usize,
+    // Every run writes its log to a file in `dir`
+    pub save: bool,
+    pub dir: PathBuf,
+    // A new file is started once the current one is this large or old
+    pub max_file_mb: u64,
+    pub max_file_hours: u64,
+    // Older files are deleted, 0 keeps all of them
+    pub keep_files: usize,
+}
+
+impl Default for LogsConfig {
+    fn default() -> Self {
+        Self {
+            memory_lines: 5000,
+            save: true,
+            dir: PathBuf::from("logs"),
+            max_file_mb: 10,
+            max_file_hours: 24,
+            keep_files: 30,
+        }
+    }
+}
+
+// Everything the server prints plus clock and operator actions, for --replay
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RecordingConfig {
+    pub enabled: bool,
+    pub dir: PathBuf,
+}
+
+impl Default for RecordingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dir: PathBuf::from("recordings"),
+        }
+    }
+}
+
+// Finished games, one JSON line each
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HistoryConfig {
+    pub save: bool,
+    pub file: PathBuf,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            save: true,
+            file: PathBuf::from("history.jsonl"),
+        }
+    }
+}
+
+// Screen settings kept between runs
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiConfig {
+    // Active tab, written whenever it changes
+    pub state_file: PathBuf,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            state_file: PathBuf::from(".servertui-state.json"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HintMode {
+    Stdin,
+    Http,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HintsConfig {
+    // Hint catalogue, the hint panel stays empty without one
+    pub file: Option<PathBuf>,
+    pub mode: HintMode,
+    // Line written to the server's stdin, `{puzzle}` and `{text}` are filled in
+    pub command: String,
+    pub http_host: String,
+    pub http_port: u16,
+    pub http_path: String,
+    pub timeout_ms: u64,
+}
+
+impl Default for HintsConfig {
+    fn default() -> Self {
+        Self {
+            file: None,
+            mode: HintMode::Stdin,
+            command: "hint {puzzle} {text}".to_string(),
+            http_host: "127.0.0.1".to_string(),
+            http_port: 8080,
+            http_path: "/hint".to_string(),
+            timeout_ms: 2000,
+        }
+    }
+}
+
+// --- Command Line ---
+
+#[derive(Debug, Default)]
+struct Cli {
+    config: Option<PathBuf>,
+    program: Option<String>,
+    args: Option<Vec<String>>,
+    cwd: Option<PathBuf>,
+    env: Vec<(String, String)>,
+    replay: Option<PathBuf>,
+}
+
+impl Cli {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut cli = Cli::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => {
+                    println!("{}", USAGE);
+                    std::process::exit(0);
+                }
+                "-c" | "--config" => cli.config = Some(PathBuf::from(value(&mut args, &arg)?)),
+                "--program" => cli.program = Some(value(&mut args, &arg)?),
+                "--arg" => {
+                    let v = value(&mut args, &arg)?;
+                    cli.args.get_or_insert_with(Vec::new).push(v);
+                }
+                "--cwd" => cli.cwd = Some(PathBuf::from(value(&mut args, &arg)?)),
+                "--replay" => cli.replay = Some(PathBuf::from(value(&mut args, &arg)?)),
+                "--env" => {
+                    let v = value(&mut args, &arg)?;
+                    let (key, val) = v
+                        .split_once('=')
+                        .with_context(|| format!("Invalid --env '{}', expected KEY=VALUE", v))?;
+                    cli.env.push((key.to_string(), val.to_string()));
+                }
+                "--" => {
+                    cli.program = Some(args.next().context("Missing program after --")?);
+                    cli.args = Some(args.by_ref().collect());
+                }
+                other => bail!("Unknown argument '{}'\n\n{}", other, USAGE),
+            }
+        }
+
+        Ok(cli)
+    }
+}
+
+// --- Loading & Validation ---
+
+impl Config {
+    // Reads the config file, applies command line overrides and validates the result.
+    // Everything here runs before raw mode, so errors reach the user as plain text.
+    pub fn load() -> Result<Self> {
+        let cli = Cli::parse(env::args().skip(1))?;
+
+        let mut config = match &cli.config {
+            Some(path) => Self::from_file(path)?,
+            None if Path::new(DEFAULT_CONFIG_FILE).is_file() => {
+                Self::from_file(Path::new(DEFAULT_CONFIG_FILE))?
+            }
+            None => Self::default(),
+        };
+
+        if let Some(program) = cli.program {
+            config.server.program = program;
+        }
+        if let Some(args) = cli.args {
+            config.server.args = args;
+        }
+        if let Some(cwd) = cli.cwd {
+            config.server.cwd = Some(cwd);
+        }
+        config.server.env.extend(cli.env);
+
+        // A replay runs no server and must not write files of its own
+        if let Some(replay) = cli.replay {
+            config.replay = Some(replay);
+            config.logs.save = false;
+            config.recording.enabled = false;
+            config.history.save = false;
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&mut self) -> Result<()> {
+        if self.replay.is_none() {
+            self.server.validate()?;
+        }
+        self.game.validate()?;
+        if self.puzzles.stale_secs > self.puzzles.offline_secs {
+            bail!("puzzles.stale_secs must not be larger than puzzles.offline_secs");
+        }
+        if self.probe.enabled && self.probe.interval_secs == 0 {
+            bail!("probe.interval_secs must be at least 1");
+        }
+        if self.probe.enabled && self.probe.timeout_ms == 0 {
+            bail!("probe.timeout_ms must be at least 1");
+        }
+        if self.process.interval_ms < 250 {
+            bail!("process.interval_ms must be at least 250");
+        }
+        if self.metrics.history_mins == 0 || self.metrics.sample_secs == 0 {
+            bail!("metrics.history_mins and metrics.sample_secs must be at least 1");
+        }
+        // The charts need two samples to span the history
+        if self.metrics.sample_secs * 2 > self.metrics.history_mins * 60 {
+            bail!("metrics.sample_secs must be at most half of metrics.history_mins");
+        }
+        if self.logs.memory_lines < 100 {
+            bail!("logs.memory_lines must be at least 100");
+        }
+        if self.logs.max_file_mb == 0 || self.logs.max_file_hours == 0 {
+            bail!("logs.max_file_mb and logs.max_file_hours must be at least 1");
+        }
+        if self.hints.timeout_ms == 0 {
+            bail!("hints.timeout_ms must be at least 1");
+        }
+        Ok(())
+    }
+
+    fn from_file(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        let mut config: Config = toml::from_str(&text)
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+
+        // Relative paths in the file are relative to the file, not to wherever we were started
+        let base = path.parent().unwrap_or(Path::new("."));
+        if let Some(cwd) = config.server.cwd.take() {
+            config.server.cwd = Some(base.join(cwd));
+        }
+        if let Some(file) = config.rules.file.take() {
+            config.rules.file = Some(base.join(file));
+        }
+        config.logs.dir = base.join(&config.logs.dir);
+        config.recording.dir = base.join(&config.recording.dir);
+        config.history.file = base.join(&config.history.file);
+        config.ui.state_file = base.join(&config.ui.state_file);
+        if let Some(file) = config.hints.file.take() {
+            config.hints.file = Some(base.join(file));
+        }
+
+        Ok(config)
+    }
+}
+
+impl ServerConfig {
+    fn validate(&mut self) -> Result<()> {
+        if self.program.trim().is_empty() {
+            bail!("No server program configured");
+        }
+
+        if let Some(cwd) = &self.cwd {
+            if !cwd.is_dir() {
+                bail!("Server working directory {} does not exist", cwd.display());
+            }
+            self.cwd = Some(cwd.canonicalize()?);
+        }
+
+        // A program with a path separator is resolved against the server's working
+        // directory, so `venv/bin/python` works together with `cwd`
+        let program = Path::new(&self.program);
+        if program.components().count() > 1 {
+            let resolved = match &self.cwd {
+                Some(cwd) if program.is_relative() => cwd.join(program),
+                _ => program.to_path_buf(),
+            };
+            if !resolved.is_file() {
+                bail!("Server program {} does not exist", resolved.display());
+            }
+            self.program = resolved.to_string_lossy().into_owned();
+        } else if find_in_path(&self.program).is_none() {
+            bail!("Server program '{}' was not found in PATH", self.program);
+        }
+
+        Ok(())
+    }
+
+    // Builds the command for the child process. Stdio is left to the caller.
+    pub fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.program);
+        cmd.args(&self.args).envs(&self.env);
+        if let Some(cwd) = &self.cwd {
+            cmd.current_dir(cwd);
+        }
+        cmd
+    }
+
+    // Human readable command line for error messages and the UI
+    pub fn display(&self) -> String {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl GameConfig {
+    fn validate(&self) -> Result<()> {
+        if self.duration_mins == 0 {
+            bail!("Game duration must be at least one minute");
+        }
+        if self.critical_mins > self.warning_mins {
+            bail!("game.critical_mins must not be larger than game.warning_mins");
+        }
+        Ok(())
+    }
+}
+
+fn value(args: &mut impl Iterator<Item = String>, name: &str) -> Result<String> {
+    args.next().with_context(|| format!("Missing value for {}", name))
+}
+
+fn find_in_path(program: &str) -> Option<PathBuf> {
+    let path = env::var_os("PATH")?;
+    find_in_dirs(env::split_paths(&path), program)
+}
+
+// Like the shell, files without the executable bit are passed over
+fn find_in_dirs(dirs: impl Iterator<Item = PathBuf>, program: &str) -> Option<PathBuf> {
+    dirs.map(|dir| dir.join(program)).find(|candidate| is_executable(candidate))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata().is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli> {
+        Cli::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    // A fresh directory per test, removed by the test when it is done
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("servertui-config-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn server(program: &str, cwd: Option<&Path>) -> ServerConfig {
+        ServerConfig { program: program.to_string(), cwd: cwd.map(Path::to_path_buf), ..ServerConfig::default() }
+    }
+
+    fn with_server(server: ServerConfig) -> Config {
+        Config { server, ..Config::default() }
+    }
+
+    fn valid() -> Config {
+        with_server(server("sh", None))
+    }
+
+    fn validate_error(config: &mut Config) -> String {
+        config.validate().unwrap_err().to_string()
+    }
+
+    // --- Command Line ---
+
+    #[test]
+    fn options_are_parsed() {
+        let args = ["-c", "room.toml", "--program", "python3", "--arg", "-u", "--arg", "server.py"];
+        let cli = parse(&[&args[..], &["--cwd", "srv", "--env", "MODE=test=1"]].concat()).unwrap();
+        assert_eq!(cli.config, Some(PathBuf::from("room.toml")));
+        assert_eq!(cli.program.as_deref(), Some("python3"));
+        assert_eq!(cli.args, Some(vec!["-u".to_string(), "server.py".to_string()]));
+        assert_eq!(cli.cwd, Some(PathBuf::from("srv")));
+        assert_eq!(cli.env, vec![("MODE".to_string(), "test=1".to_string())]);
+    }
+
+    #[test]
+    fn everything_after_dashes_is_the_command() {
+        let cli = parse(&["--arg", "ignored", "--", "./venv/bin/python", "-u", "--config"]).unwrap();
+        assert_eq!(cli.program.as_deref(), Some("./venv/bin/python"));
+        assert_eq!(cli.args, Some(vec!["-u".to_string(), "--config".to_string()]));
+        assert!(parse(&["--"]).unwrap_err().to_string().contains("Missing program after --"));
+    }
+
+    #[test]
+    fn unknown_argument_is_rejected() {
+        let error = parse(&["--verbose"]).unwrap_err().to_string();
+        assert!(error.starts_with("Unknown argument '--verbose'"));
+        assert!(error.contains("Usage:"));
+    }
+
+    #[test]
+    fn missing_values_are_rejected() {
+        for option in ["-c", "--config", "--program", "--arg", "--cwd", "--env"] {
+            let error = parse(&["--program", "sh", option]).unwrap_err().to_string();
+            assert_eq!(error, format!("Missing value for {}", option));
+        }
+        let error = parse(&["--env", "MODE"]).unwrap_err().to_string();
+        assert_eq!(error, "Invalid --env 'MODE', expected KEY=VALUE");
+    }
+
+    // --- Validation ---
+
+    #[test]
+    fn empty_program_is_rejected() {
+        let mut config = with_server(server("  ", None));
+        assert_eq!(validate_error(&mut config), "No server program configured");
+    }
+
+    #[test]
+    fn missing_cwd_is_rejected() {
+        let cwd = env::temp_dir().join("servertui-config-no-such-dir");
+        let mut config = with_server(server("sh", Some(&cwd)));
+        assert_eq!(validate_error(&mut config), format!("Server working directory {} does not exist", cwd.display()));
+    }
+
+    #[test]
+    fn program_path_is_resolved_against_cwd() {
+        let dir = temp_dir("resolve");
+        std::fs::create_dir(dir.join("bin")).unwrap();
+        std::fs::write(dir.join("bin/server"), "").unwrap();
+
+        let mut config = with_server(server("bin/server", Some(&dir)));
+        config.validate().unwrap();
+        assert_eq!(Path::new(&config.server.program), dir.canonicalize().unwrap().join("bin/server"));
+
+        let mut config = with_server(server("bin/missing", Some(&dir)));
+        let missing = dir.canonicalize().unwrap().join("bin/missing");
+        assert_eq!(validate_error(&mut config), format!("Server program {} does not exist", missing.display()));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn program_must_be_in_path() {
+        valid().validate().unwrap();
+
+        let mut config = with_server(server("servertui-no-such-program", None));
+        assert_eq!(validate_error(&mut config), "Server program 'servertui-no-such-program' was not found in PATH");
+    }
+
+    #[test]
+    fn replay_needs_no_server() {
+        let mut config = with_server(server("servertui-no-such-program", None));
+        config.replay = Some(PathBuf::from("session.jsonl"));
+        config.validate().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn path_lookup_skips_files_that_are_not_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = temp_dir("path");
+        let program = dir.join("server");
+        std::fs::write(&program, "").unwrap();
+        std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o644)).unwrap();
+        assert_eq!(find_in_dirs(std::iter::once(dir.clone()), "server"), None);
+
+        std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(find_in_dirs(std::iter::once(dir.clone()), "server"), Some(program));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn game_times_are_checked() {
+        let mut config = valid();
+        config.game.duration_mins = 0;
+        assert_eq!(validate_error(&mut config), "Game duration must be at least one minute");
+
+        let mut config = valid();
+        config.game.critical_mins = config.game.warning_mins + 1;
+        assert_eq!(validate_error(&mut config), "game.critical_mins must not be larger than game.warning_mins");
+    }
+
+    #[test]
+    fn stale_must_come_before_offline() {
+        let mut config = valid();
+        config.puzzles.stale_secs = config.puzzles.offline_secs + 1;
+        assert_eq!(validate_error(&mut config), "puzzles.stale_secs must not be larger than puzzles.offline_secs");
+    }
+
+    #[test]
+    fn enabled_probe_needs_interval_and_timeout() {
+        let mut config = valid();
+        config.probe.interval_secs = 0;
+        config.probe.timeout_ms = 0;
+        config.validate().unwrap();
+
+        config.probe.enabled = true;
+        assert_eq!(validate_error(&mut config), "probe.interval_secs must be at least 1");
+        config.probe.interval_secs = 5;
+        assert_eq!(validate_error(&mut config), "probe.timeout_ms must be at least 1");
+    }
+
+    #[test]
+    fn memory_lines_has_a_minimum() {
+        let mut config = valid();
+        config.logs.memory_lines = 99;
+        assert_eq!(validate_error(&mut config), "logs.memory_lines must be at least 100");
+    }
+
+
+    #[test]
+    fn log_files_need_a_size_and_age() {
+        let mut config = valid();
+        config.logs.max_file_hours = 0;
+        assert_eq!(
+            validate_error(&mut config),
+            "logs.max_file_mb and logs.max_file_hours must be at least 1"
+        );
+    }
+
+
+    #[test]
+    fn hint_timeout_has_a_minimum() {
+        let mut config = valid();
+        config.hints.timeout_ms = 0;
+        assert_eq!(validate_error(&mut config), "hints.timeout_ms must be at least 1");
+    }
+
+
+    #[test]
+    fn process_interval_has_a_minimum() {
+        let mut config = valid();
+        config.process.interval_ms = 249;
+        assert_eq!(validate_error(&mut config), "process.interval_ms must be at least 250");
+    }
+
+
+    #[test]
+    fn metrics_need_two_samples_in_the_history() {
+        let mut config = valid();
+        config.metrics.sample_secs = 0;
+        assert_eq!(validate_error(&mut config), "metrics.history_mins and metrics.sample_secs must be at least 1");
+
+        config.metrics.history_mins = 1;
+        config.metrics.sample_secs = 30;
+        config.validate().unwrap();
+        config.metrics.sample_secs = 31;
+        assert_eq!(validate_error(&mut config), "metrics.sample_secs must be at most half of metrics.history_mins");
+    }
+}
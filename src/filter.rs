@@ -0,0 +1,334 @@
+use crate::{
+    logbuffer::LogBuffer,
+    logs::{LogEntry, LogLevel, LogSource},
+    puzzle::{contains_token, Puzzle},
+};
+use anyhow::{bail, Context, Result};
+use regex::{Regex, RegexBuilder};
+use std::collections::HashMap;
+
+// --- Log Filter ---
+//
+// Typed into the filter bar as space separated terms, e.g.
+//   level:warning source:stderr puzzle:safe ip:10.0.0.5 timeout|refused
+// Terms without a prefix are joined into one case-insensitive regex.
+
+#[derive(Default)]
+pub struct LogFilter {
+    // As typed, shown in the filter bar
+    pub text: String,
+    regex: Option<Regex>,
+    min_level: Option<LogLevel>,
+    source: Option<LogSource>,
+    puzzle: Option<String>,
+    ip: Option<String>,
+}
+
+impl LogFilter {
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut filter = Self { text: text.trim().to_string(), ..Self::default() };
+        let mut pattern = Vec::new();
+
+        for term in text.split_whitespace() {
+            match term.split_once(':') {
+                Some(("level", value)) => {
+                    let level = LogLevel::from_name(&value.to_uppercase())
+                        .with_context(|| format!("Unknown level '{}'", value))?;
+                    filter.min_level = Some(level);
+                }
+                Some(("source", value)) => {
+                    let source = LogSource::from_name(&value.to_lowercase())
+                        .with_context(|| format!("Unknown source '{}'", value))?;
+                    filter.source = Some(source);
+                }
+                Some(("puzzle", value)) if !value.is_empty() => filter.puzzle = Some(value.to_string()),
+                Some(("ip", value)) if !value.is_empty() => filter.ip = Some(value.to_string()),
+                Some(("puzzle" | "ip", _)) => bail!("Missing value in '{}'", term),
+                _ => pattern.push(term),
+            }
+        }
+
+        if !pattern.is_empty() {
+            filter.regex = Some(search_regex(&pattern.join(" "))?);
+        }
+        Ok(filter)
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.text.is_empty()
+    }
+
+    pub fn matches(&self, entry: &LogEntry, puzzles: &HashMap<String, Puzzle>) -> bool {
+        // Lines without a level count as INFO
+        if let Some(min_level) = self.min_level
+            && entry.level.unwrap_or(LogLevel::Info) < min_level
+        {
+            return false;
+        }
+        if self.source.is_some_and(|s| s != entry.source) {
+            return false;
+        }
+        if let Some(regex) = &self.regex
+            && !entry.contains(regex)
+        {
+            return false;
+        }
+        if let Some(ip) = &self.ip
+            && !entry.lines().any(|l| contains_token(l, ip, |c| c.is_ascii_digit() || c == '.'))
+        {
+            return false;
+        }
+        if let Some(name) = &self.puzzle {
+            // A known puzzle also matches by its IP
+            let mentioned = match puzzles.get(name) {
+                Some(puzzle) => entry.lines().any(|l| puzzle.is_mentioned(l)),
+                None => entry.lines().any(|l| contains_token(l, name, |c| c.is_alphanumeric() || c == '_')),
+            };
+            if !mentioned {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+// Search and filter patterns ignore case, they are typed quickly during a game
+pub fn search_regex(pattern: &str) -> Result<Regex> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .with_context(|| format!("Invalid pattern '{}'", pattern))
+}
+
+// --- Filtered View ---
+
+// Entries passing the filter and the search matches among them. Kept up to date
+// incrementally, so spilled lines are only read again when filter or search change.
+#[derive(Default)]
+pub struct LogView {
+    // Log indices of the visible entries, `None` while no filter is active
+    visible: Option<Vec<usize>>,
+    matches: Vec<usize>,
+    // Entries before this index have been checked
+    scanned: usize,
+}
+
+impl LogView {
+    // Forgets everything, the next update scans the whole log again
+    pub fn invalidate(&mut self) {
+        *self = Self::default();
+    }
+
+    // Checks new entries, and again those from `changed` on (e.g. a traceback that got more lines)
+    pub fn update(
+        &mut self,
+        logs: &LogBuffer,
+        changed: Option<usize>,
+        filter: &LogFilter,
+        search: Option<&Regex>,
+        puzzles: &HashMap<String, Puzzle>,
+    ) {
+        if filter.is_active() != self.visible.is_some() {
+            self.invalidate();
+            if filter.is_active() {
+                self.visible = Some(Vec::new());
+            }
+        }
+
+        let start = changed.map_or(self.scanned, |c| c.min(self.scanned));
+        if let Some(visible) = &mut self.visible {
+            visible.truncate(visible.partition_point(|&i| i < start));
+        }
+        self.matches.truncate(self.matches.partition_point(|&i| i < start));
+
+        // Nothing to check, and spilled lines would be read back for nothing
+        if self.visible.is_none() && search.is_none() {
+            self.scanned = logs.len();
+            return;
+        }
+
+        for (index, entry) in logs.iter_from(start) {
+            if let Some(visible) = &mut self.visible {
+                if !filter.matches(&entry, puzzles) {
+                    continue;
+                }
+                visible.push(index);
+            }
+            if search.is_some_and(|s| entry.contains(s)) {
+                self.matches.push(index);
+            }
+        }
+        self.scanned = logs.len();
+    }
+
+    // Number of visible entries
+    pub fn len(&self) -> usize {
+        self.visible.as_ref().map_or(self.scanned, Vec::len)
+    }
+
+    // Log index of the entry at a position in the view
+    pub fn index(&self, position: usize) -> usize {
+        self.visible.as_ref().map_or(position, |v| v[position])
+    }
+
+    pub fn position(&self, index: usize) -> Option<usize> {
+        match &self.visible {
+            Some(visible) => visible.binary_search(&index).ok(),
+            None => (index < self.scanned).then_some(index),
+        }
+    }
+
+    // Log indices of the search matches, oldest first
+    pub fn matches(&self) -> &[usize] {
+        &self.matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(source: LogSource, level: Option<LogLevel>, text: &str) -> LogEntry {
+        LogEntry::new(source, level, text.to_string())
+    }
+
+    fn stdout(text: &str) -> LogEntry {
+        entry(LogSource::Stdout, None, text)
+    }
+
+    fn matches(filter: &str, entry: &LogEntry) -> bool {
+        LogFilter::parse(filter).unwrap().matches(entry, &HashMap::new())
+    }
+
+    #[test]
+    fn level_is_a_minimum() {
+        assert!(matches("level:warning", &entry(LogSource::Stdout, Some(LogLevel::Error), "boom")));
+        assert!(matches("level:WARN", &entry(LogSource::Stdout, Some(LogLevel::Warning), "hm")));
+        assert!(!matches("level:warning", &entry(LogSource::Stdout, Some(LogLevel::Info), "ok")));
+        // Lines without a level count as INFO
+        assert!(matches("level:info", &stdout("plain")));
+        assert!(!matches("level:error", &stdout("plain")));
+    }
+
+    #[test]
+    fn source_filter() {
+        assert!(matches("source:stderr", &entry(LogSource::Stderr, None, "oops")));
+        assert!(!matches("source:stderr", &stdout("fine")));
+        assert!(matches("source:SUPERVISOR", &entry(LogSource::Supervisor, None, "Started")));
+    }
+
+    #[test]
+    fn puzzle_filter_matches_whole_names() {
+        assert!(matches("puzzle:safe", &stdout("Puzzle safe was solved")));
+        assert!(matches("puzzle:safe", &stdout("{'name': 'safe'}")));
+        assert!(!matches("puzzle:safe", &stdout("Puzzle safe2 was solved")));
+        assert!(!matches("puzzle:safe", &stdout("Puzzle unsafe was solved")));
+    }
+
+    #[test]
+    fn puzzle_filter_matches_the_ip_of_known_puzzles() {
+        let mut puzzles = HashMap::new();
+        puzzles.insert("safe".to_string(), Puzzle::new("safe".to_string(), "10.0.0.6".to_string()));
+        let filter = LogFilter::parse("puzzle:safe").unwrap();
+        assert!(filter.matches(&stdout("10.0.0.6 - - \"GET /state HTTP/1.1\" 200"), &puzzles));
+        assert!(!filter.matches(&stdout("10.0.0.66 - - \"GET /state HTTP/1.1\" 200"), &puzzles));
+    }
+
+    #[test]
+    fn ip_filter_matches_whole_addresses() {
+        assert!(matches("ip:10.0.0.5", &stdout("from 10.0.0.5:4000")));
+        assert!(!matches("ip:10.0.0.5", &stdout("from 10.0.0.50")));
+    }
+
+    #[test]
+    fn terms_are_combined() {
+        let line = entry(LogSource::Stderr, Some(LogLevel::Error), "Connection REFUSED by laser");
+        assert!(matches("source:stderr level:error timeout|refused", &line));
+        assert!(!matches("source:stdout timeout|refused", &line));
+        assert!(!matches("puzzle:safe refused", &line));
+    }
+
+    #[test]
+    fn invalid_terms_are_rejected() {
+        let error = format!("{:#}", LogFilter::parse("level:loud").err().unwrap());
+        assert!(error.contains("Unknown level 'loud'"), "{}", error);
+        assert!(LogFilter::parse("source:radio").is_err());
+        assert!(LogFilter::parse("puzzle:").is_err());
+        let error = format!("{:#}", LogFilter::parse("timeout(").err().unwrap());
+        assert!(error.contains("Invalid pattern 'timeout('"), "{}", error);
+    }
+
+    #[test]
+    fn empty_filter_is_inactive() {
+        let filter = LogFilter::parse("  ").unwrap();
+        assert!(!filter.is_active());
+        assert!(filter.matches(&stdout("anything"), &HashMap::new()));
+    }
+
+    // Small enough to spill, so the view also reads entries back from disk
+    fn spilled_log(lines: &[&str]) -> LogBuffer {
+        let mut logs = LogBuffer::new(4);
+        for line in lines {
+            logs.push(stdout(line));
+        }
+        assert!(logs.get_mut(0).is_none());
+        logs
+    }
+
+    #[test]
+    fn view_follows_filter_and_search() {
+        let logs = spilled_log(&["safe open", "tick", "laser ERROR", "tick", "safe solved", "tick", "error again"]);
+        let puzzles = HashMap::new();
+        let mut view = LogView::default();
+
+        view.update(&logs, None, &LogFilter::default(), None, &puzzles);
+        assert_eq!(view.len(), 7);
+        assert_eq!(view.index(4), 4);
+
+        let filter = LogFilter::parse("puzzle:safe").unwrap();
+        view.update(&logs, None, &filter, None, &puzzles);
+        assert_eq!(view.len(), 2);
+        assert_eq!((view.index(0), view.index(1)), (0, 4));
+        assert_eq!(view.position(4), Some(1));
+        assert_eq!(view.position(2), None);
+
+        let search = search_regex("error").unwrap();
+        view.invalidate();
+        view.update(&logs, None, &LogFilter::default(), Some(&search), &puzzles);
+        assert_eq!(view.matches(), [2, 6]);
+
+        // Clearing the search leaves a plain view of everything
+        view.invalidate();
+        view.update(&logs, None, &LogFilter::default(), None, &puzzles);
+        assert_eq!(view.len(), 7);
+        assert!(view.matches().is_empty());
+    }
+
+    #[test]
+    fn view_checks_new_and_changed_entries() {
+        let mut logs = spilled_log(&["safe open", "tick", "tick", "tick", "tick", "tick"]);
+        let puzzles = HashMap::new();
+        let filter = LogFilter::parse("puzzle:safe").unwrap();
+        let mut view = LogView::default();
+        view.update(&logs, None, &filter, None, &puzzles);
+        assert_eq!(view.len(), 1);
+
+        logs.push(stdout("safe solved"));
+        view.update(&logs, None, &filter, None, &puzzles);
+        assert_eq!(view.len(), 2);
+
+        // The last tick turned into a line about the safe
+        let last = logs.len() - 2;
+        logs.get_mut(last).unwrap().text = "safe reset".to_string();
+        view.update(&logs, Some(last), &filter, None, &puzzles);
+        assert_eq!(view.len(), 3);
+        assert_eq!(view.index(1), last);
+    }
+
+    #[test]
+    fn search_ignores_case() {
+        let regex = search_regex("Traceback").unwrap();
+        assert!(regex.is_match("traceback (most recent call last)"));
+        assert!(search_regex("[").is_err());
+    }
+}
@@ -0,0 +1,237 @@
+use crate::hints::GivenHint;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+// --- Game Sessions ---
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GameResult {
+    Escaped,
+    Failed,
+}
+
+impl GameResult {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameResult::Escaped => "ESCAPED",
+            GameResult::Failed => "FAILED",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            GameResult::Escaped => Color::Green,
+            GameResult::Failed => Color::Red,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SolvedPuzzle {
+    pub puzzle: String,
+    pub game_secs: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct UsedHint {
+    pub puzzle: String,
+    pub text: String,
+    // `None` if the hint was given while the clock was not running
+    pub game_secs: Option<u64>,
+}
+
+// A game in progress, from `g` until it is ended with a result
+pub struct Game {
+    pub team: String,
+    pub players: u32,
+    pub started: DateTime<Local>,
+    pub solved: Vec<SolvedPuzzle>,
+}
+
+impl Game {
+    pub fn new(team: String, players: u32, started: DateTime<Local>) -> Self {
+        Self { team, players, started, solved: Vec::new() }
+    }
+
+    // A puzzle solved again after a reset keeps only its latest time
+    pub fn puzzle_solved(&mut self, puzzle: &str, game_time: Duration) {
+        self.solved.retain(|s| s.puzzle != puzzle);
+        self.solved.push(SolvedPuzzle { puzzle: puzzle.to_string(), game_secs: game_time.as_secs() });
+    }
+
+    // `puzzles` are all puzzles known at the end, those not solved in this game count as unsolved
+    pub fn finish(
+        self,
+        result: GameResult,
+        ended: DateTime<Local>,
+        total: Duration,
+        hints: &[GivenHint],
+        puzzles: impl IntoIterator<Item = String>,
+    ) -> GameRecord {
+        let mut unsolved: Vec<String> =
+            puzzles.into_iter().filter(|p| !self.solved.iter().any(|s| s.puzzle == *p)).collect();
+        unsolved.sort();
+        GameRecord {
+            team: self.team,
+            players: self.players,
+            started: self.started,
+            ended,
+            result,
+            total_secs: total.as_secs(),
+            solved: self.solved,
+            unsolved,
+            hints: hints
+                .iter()
+                .map(|h| UsedHint {
+                    puzzle: h.puzzle.clone(),
+                    text: h.text.clone(),
+                    game_secs: h.game_time.map(|t| t.as_secs()),
+                })
+                .collect(),
+        }
+    }
+}
+
+// "Die Füchse, 4" as typed into the new game prompt
+pub fn parse_team(input: &str) -> Result<(String, u32)> {
+    let Some((team, players)) = input.rsplit_once(',') else {
+        bail!("Expected `<team>, <players>`");
+    };
+    let team = team.trim();
+    if team.is_empty() {
+        bail!("Missing team name");
+    }
+    let players = players.trim();
+    match players.parse::<u32>() {
+        Ok(n) if n > 0 => Ok((team.to_string(), n)),
+        _ => bail!("Invalid player count '{}'", players),
+    }
+}
+
+// --- History ---
+//
+// One finished game per line in the history file, e.g.
+//   {"team":"Die Füchse","players":4,"started":"...","ended":"...","result":"escaped","total_secs":3132,...}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub team: String,
+    pub players: u32,
+    pub started: DateTime<Local>,
+    pub ended: DateTime<Local>,
+    pub result: GameResult,
+    pub total_secs: u64,
+    pub solved: Vec<SolvedPuzzle>,
+    #[serde(default)]
+    pub unsolved: Vec<String>,
+    pub hints: Vec<UsedHint>,
+}
+
+#[derive(Default)]
+pub struct History {
+    // `None` while replaying, games are then only kept in memory
+    path: Option<PathBuf>,
+    // Oldest first
+    pub games: Vec<GameRecord>,
+    // Lines that could not be read, e.g. cut off by a crash while writing
+    pub skipped: usize,
+}
+
+impl History {
+    pub fn load(path: &Path, save: bool) -> Result<Self> {
+        let mut games = Vec::new();
+        let mut skipped = 0;
+        match std::fs::read_to_string(path) {
+            Ok(text) => {
+                for line in text.lines().filter(|l| !l.trim().is_empty()) {
+                    match serde_json::from_str(line) {
+                        Ok(game) => games.push(game),
+                        Err(_) => skipped += 1,
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e).with_context(|| format!("Failed to read history {}", path.display())),
+        }
+        Ok(Self { path: save.then(|| path.to_path_buf()), games, skipped })
+    }
+
+    pub fn append(&mut self, game: GameRecord) -> Result<()> {
+        let result = match &self.path {
+            Some(path) => write_record(path, &game),
+            None => Ok(()),
+        };
+        self.games.push(game);
+        result
+    }
+}
+
+fn write_record(path: &Path, game: &GameRecord) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut line = serde_json::to_vec(game)?;
+    line.push(b'\n');
+    OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(&line))
+        .with_context(|| format!("Failed to write history {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn team_and_players_are_parsed() {
+        assert_eq!(parse_team(" Die Füchse , 4 ").unwrap(), ("Die Füchse".to_string(), 4));
+        assert_eq!(parse_team("Tick, Trick, 3").unwrap(), ("Tick, Trick".to_string(), 3));
+        assert_eq!(parse_team("Die Füchse").unwrap_err().to_string(), "Expected `<team>, <players>`");
+        assert_eq!(parse_team(" , 4").unwrap_err().to_string(), "Missing team name");
+        assert_eq!(parse_team("Die Füchse, 0").unwrap_err().to_string(), "Invalid player count '0'");
+    }
+
+    #[test]
+    fn solving_again_keeps_the_latest_time() {
+        let mut game = Game::new("Die Füchse".to_string(), 4, Local::now());
+        game.puzzle_solved("safe", Duration::from_secs(300));
+        game.puzzle_solved("laser", Duration::from_secs(600));
+        game.puzzle_solved("safe", Duration::from_secs(900));
+        let solved: Vec<(&str, u64)> = game.solved.iter().map(|s| (s.puzzle.as_str(), s.game_secs)).collect();
+        assert_eq!(solved, [("laser", 600), ("safe", 900)]);
+    }
+
+    #[test]
+    fn history_is_read_back_and_skips_broken_lines() {
+        let path = std::env::temp_dir().join(format!("servertui-test-{}-history.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut history = History::load(&path, true).unwrap();
+        assert!(history.games.is_empty());
+        for (team, result) in [("Die Füchse", GameResult::Escaped), ("Die Eulen", GameResult::Failed)] {
+            let game = Game::new(team.to_string(), 4, Local::now());
+            history.append(game.finish(result, Local::now(), Duration::from_secs(3132), &[], Vec::new())).unwrap();
+        }
+        // A line cut off by a crash
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(b"{\"team\":\"Die").unwrap();
+
+        let history = History::load(&path, false).unwrap();
+        assert_eq!(history.skipped, 1);
+        let teams: Vec<&str> = history.games.iter().map(|g| g.team.as_str()).collect();
+        assert_eq!(teams, ["Die Füchse", "Die Eulen"]);
+        assert!(history.games[1].result == GameResult::Failed);
+        assert_eq!(history.games[1].total_secs, 3132);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,242 @@
+use crate::{
+    clock::format_game_time,
+    config::{HintMode, HintsConfig},
+    logs::{LogLevel, LogSource},
+    supervisor::Supervisor,
+    App,
+};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local};
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, BufReader, Write},
+    net::{TcpStream, ToSocketAddrs},
+    path::Path,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+// --- Hint Catalogue ---
+//
+// A TOML file with one list of hints per puzzle, in the order they are usually given:
+//   safe = ["Schaut euch das Bild genauer an.", "Die Zahlen ergeben den Code."]
+
+#[derive(Default)]
+pub struct HintCatalogue {
+    hints: BTreeMap<String, Vec<String>>,
+}
+
+impl HintCatalogue {
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read hint file {}", path.display()))?;
+        let hints: BTreeMap<String, Vec<String>> =
+            toml::from_str(&text).with_context(|| format!("Invalid hint file {}", path.display()))?;
+
+        // Both end up in a single line on the server's stdin
+        for (puzzle, texts) in &hints {
+            if puzzle.contains(['\n', '\r']) {
+                bail!("Invalid hint file {}: puzzle name {:?} contains a line break", path.display(), puzzle);
+            }
+            if let Some(text) = texts.iter().find(|t| t.contains(['\n', '\r'])) {
+                bail!("Invalid hint file {}: hint {:?} for {} contains a line break", path.display(), text, puzzle);
+            }
+        }
+        Ok(Self { hints })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hints.is_empty()
+    }
+
+    pub fn puzzles(&self) -> impl Iterator<Item = &String> {
+        self.hints.keys()
+    }
+
+    pub fn hints(&self, puzzle: &str) -> &[String] {
+        self.hints.get(puzzle).map_or(&[], Vec::as_slice)
+    }
+}
+
+// A hint given during the current game
+pub struct GivenHint {
+    pub puzzle: String,
+    pub text: String,
+    pub at: DateTime<Local>,
+    pub game_time: Option<Duration>,
+}
+
+impl GivenHint {
+    // "@ 12:34" on the game clock, the wall clock time if no game was running
+    pub fn time_label(&self) -> String {
+        match self.game_time {
+            Some(game_time) => format!("@ {}", format_game_time(game_time)),
+            None => format!("@ {}", self.at.format("%H:%M:%S")),
+        }
+    }
+}
+
+// Selection in the hint panel: index into the catalogue puzzles and their hints
+#[derive(Default)]
+pub struct HintPanel {
+    pub puzzle: usize,
+    pub hint: usize,
+}
+
+// --- Delivery ---
+
+// Sends a hint the configured way and counts it once it got through. Runs after
+// the app lock is released, the supervisor and the HTTP thread lock the app themselves.
+pub fn deliver(config: &HintsConfig, puzzle: &str, text: &str, supervisor: Option<&mut Supervisor>, app: &Arc<Mutex<App>>) {
+    match config.mode {
+        HintMode::Stdin => match supervisor {
+            // The supervisor logs its own errors
+            Some(supervisor) => {
+                if supervisor.send(&command(config, puzzle, text)) {
+                    app.lock().unwrap().hint_delivered(puzzle.to_string(), text.to_string());
+                }
+            }
+            None => failed(app, puzzle, "server is not running"),
+        },
+        HintMode::Http => {
+            let (config, puzzle, text, app) = (config.clone(), puzzle.to_string(), text.to_string(), app.clone());
+            thread::spawn(move || match post(&config, &puzzle, &text) {
+                Ok(()) => app.lock().unwrap().hint_delivered(puzzle, text),
+                Err(e) => failed(&app, &puzzle, &format!("{:#}", e)),
+            });
+        }
+    }
+}
+
+fn failed(app: &Arc<Mutex<App>>, puzzle: &str, error: &str) {
+    app.lock().unwrap().log_marked(
+        LogSource::Supervisor,
+        LogLevel::Error,
+        format!("Failed to send hint for {}: {}", puzzle, error),
+    );
+}
+
+// The stdin line, e.g. "hint safe Die Zahlen ergeben den Code."
+fn command(config: &HintsConfig, puzzle: &str, text: &str) -> String {
+    config.command.replace("{puzzle}", puzzle).replace("{text}", text)
+}
+
+// POST {"puzzle": ..., "text": ...} to the game server, any 2xx counts as delivered
+fn post(config: &HintsConfig, puzzle: &str, text: &str) -> Result<()> {
+    let timeout = Duration::from_millis(config.timeout_ms);
+    let addr = (config.http_host.as_str(), config.http_port)
+        .to_socket_addrs()?
+        .next()
+        .with_context(|| format!("Cannot resolve {}", config.http_host))?;
+    let mut stream = TcpStream::connect_timeout(&addr, timeout)
+        .with_context(|| format!("Cannot connect to {}", addr))?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+
+    let body = serde_json::json!({ "puzzle": puzzle, "text": text }).to_string();
+    let request = format!(
+        "POST {} HTTP/1.0\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        config.http_path,
+        config.http_host,
+        body.len(),
+        body
+    );
+    stream.write_all(request.as_bytes())?;
+
+    let mut status_line = String::new();
+    BufReader::new(stream).read_line(&mut status_line)?;
+    check_status(&status_line)
+}
+
+// "HTTP/1.1 204 No Content"
+fn check_status(status_line: &str) -> Result<()> {
+    let status = status_line.split_whitespace().nth(1).unwrap_or("");
+    if status.len() != 3 || !status.starts_with('2') {
+        bail!("HTTP status {}", if status.is_empty() { "missing" } else { status });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::Read, net::TcpListener};
+
+    fn load(name: &str, text: &str) -> Result<HintCatalogue> {
+        let path = std::env::temp_dir().join(format!("servertui-test-{}-{}.toml", std::process::id(), name));
+        std::fs::write(&path, text).unwrap();
+        let catalogue = HintCatalogue::load(Some(&path));
+        std::fs::remove_file(&path).unwrap();
+        catalogue
+    }
+
+    #[test]
+    fn catalogue_keeps_the_order_of_the_hints() {
+        assert!(HintCatalogue::load(None).unwrap().is_empty());
+
+        let catalogue = load("hints", "safe = [\"Das Bild\", \"Die Zahlen\"]\nlaser = [\"Spiegel\"]\n").unwrap();
+        assert_eq!(catalogue.puzzles().collect::<Vec<_>>(), ["laser", "safe"]);
+        assert_eq!(catalogue.hints("safe"), ["Das Bild", "Die Zahlen"]);
+        assert!(catalogue.hints("door").is_empty());
+    }
+
+    #[test]
+    fn line_breaks_are_rejected() {
+        let error = load("hint-break", "safe = [\"Das Bild\\nund mehr\"]\n").err().unwrap();
+        assert!(error.to_string().contains("hint \"Das Bild\\nund mehr\" for safe contains a line break"));
+        let error = load("name-break", "\"sa\\rfe\" = [\"Das Bild\"]\n").err().unwrap();
+        assert!(error.to_string().contains("puzzle name \"sa\\rfe\" contains a line break"));
+        assert!(load("invalid", "safe = \"Das Bild\"\n").is_err());
+    }
+
+    #[test]
+    fn command_fills_in_puzzle_and_text() {
+        let mut config = HintsConfig::default();
+        assert_eq!(command(&config, "safe", "Die Zahlen"), "hint safe Die Zahlen");
+        config.command = "say {text} ({puzzle}, {puzzle})".to_string();
+        assert_eq!(command(&config, "safe", "Hallo"), "say Hallo (safe, safe)");
+    }
+
+    #[test]
+    fn only_2xx_counts_as_delivered() {
+        check_status("HTTP/1.1 200 OK\r\n").unwrap();
+        check_status("HTTP/1.0 204 No Content\r\n").unwrap();
+        assert_eq!(check_status("HTTP/1.1 404 Not Found\r\n").unwrap_err().to_string(), "HTTP status 404");
+        assert_eq!(check_status("HTTP/1.1 2000\r\n").unwrap_err().to_string(), "HTTP status 2000");
+        assert_eq!(check_status("").unwrap_err().to_string(), "HTTP status missing");
+    }
+
+    #[test]
+    fn hint_is_posted_as_json() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let config = HintsConfig {
+            mode: HintMode::Http,
+            http_port: listener.local_addr().unwrap().port(),
+            ..HintsConfig::default()
+        };
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            let mut buf = [0u8; 1024];
+            while !request.contains('}') {
+                let n = stream.read(&mut buf).unwrap();
+                request.push_str(&String::from_utf8_lossy(&buf[..n]));
+            }
+            // The status line arrives in two parts
+            stream.write_all(b"HTTP/1.1 2").unwrap();
+            stream.flush().unwrap();
+            thread::sleep(Duration::from_millis(20));
+            stream.write_all(b"04 No Content\r\n\r\n").unwrap();
+            request
+        });
+
+        post(&config, "safe", "Die \"Zahlen\"").unwrap();
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /hint HTTP/1.0\r\n"));
+        assert!(request.ends_with("\r\n\r\n{\"puzzle\":\"safe\",\"text\":\"Die \\\"Zahlen\\\"\"}"));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{
+    puzzle::Registration,
+    rules::{Action, ClientKind, Hit},
+};
+use serde_json::{Map, Value};
+
+// --- JSON Log Lines ---
+//
+// The newer server.py prints one object per line, e.g.
+//   {"level": "INFO", "event": "puzzle_solved", "puzzle": "safe", "ip": "10.0.0.6"}
+// Known events map onto the same actions as the rule file.
+
+pub struct JsonLine {
+    pub hits: Vec<Hit>,
+    pub level: Option<String>,
+    // Compact, readable form for the log pane
+    pub display: String,
+}
+
+// Fields that are shown up front or not at all, everything else is listed as key=value
+const SHOWN_FIELDS: [&str; 6] = ["ts", "time", "level", "event", "message", "msg"];
+
+pub fn parse(line: &str) -> Option<JsonLine> {
+    let trimmed = line.trim();
+    if !trimmed.starts_with('{') {
+        return None;
+    }
+    let Ok(Value::Object(fields)) = serde_json::from_str::<Value>(trimmed) else {
+        return None;
+    };
+
+    Some(JsonLine {
+        hits: hits(&fields),
+        level: text(&fields, "level"),
+        display: display(&fields),
+    })
+}
+
+fn text(fields: &Map<String, Value>, key: &str) -> Option<String> {
+    match fields.get(key)? {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+fn hits(fields: &Map<String, Value>) -> Vec<Hit> {
+    let event = text(fields, "event").unwrap_or_default();
+    let message = text(fields, "message")
+        .or_else(|| text(fields, "msg"))
+        .unwrap_or_else(|| event.clone());
+
+    let action = match event.as_str() {
+        "server_ready" => Action::MarkServerReady,
+        "puzzle_registered" => Action::RegisterPuzzle,
+        "puzzle_ip" => Action::UpdatePuzzleIp,
+        "puzzle_active" => Action::MarkActive,
+        "puzzle_solved" => Action::MarkSolved,
+        "puzzle_reset" => Action::MarkReset,
+        "puzzle_error" => Action::MarkError,
+        "http_request" => Action::RegisterClient { kind: ClientKind::Http },
+        "udp_message" => Action::RegisterClient { kind: ClientKind::Udp },
+        "alert" => Action::RaiseAlert { message: None },
+        _ => return Vec::new(),
+    };
+
+    // The attributes of a registration are all fields but the general ones
+    let registration = matches!(action, Action::RegisterPuzzle).then(|| {
+        let attributes = fields.iter().filter(|(key, _)| !SHOWN_FIELDS.contains(&key.as_str()));
+        Registration::Fields(attributes.map(|(key, value)| (key.clone(), value.clone())).collect())
+    });
+
+    vec![Hit {
+        action,
+        name: text(fields, "puzzle"),
+        ip: text(fields, "ip"),
+        path: text(fields, "path"),
+        value: text(fields, "value"),
+        message,
+        registration,
+    }]
+}
+
+// "12:00:01 INFO  puzzle_solved  solved in 3 tries · puzzle=safe ip=10.0.0.6"
+fn display(fields: &Map<String, Value>) -> String {
+    let mut parts = Vec::new();
+    if let Some(ts) = text(fields, "ts").or_else(|| text(fields, "time")) {
+        parts.push(ts);
+    }
+    if let Some(level) = text(fields, "level") {
+        parts.push(format!("{:<8}", level.to_uppercase()));
+    }
+    if let Some(event) = text(fields, "event") {
+        parts.push(event);
+    }
+    if let Some(message) = text(fields, "message").or_else(|| text(fields, "msg")) {
+        parts.push(message);
+    }
+
+    let extra: Vec<String> = fields.iter()
+        .filter(|(key, _)| !SHOWN_FIELDS.contains(&key.as_str()))
+        .map(|(key, value)| match value {
+            Value::String(s) => format!("{}={}", key, s),
+            other => format!("{}={}", key, other),
+        })
+        .collect();
+
+    let mut out = parts.join("  ");
+    if !extra.is_empty() {
+        out.push_str(" · ");
+        out.push_str(&extra.join(" "));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_lines_are_not_json() {
+        assert!(parse("Puzzle registered: {'name': 'safe'}").is_none());
+        assert!(parse("{not json").is_none());
+        assert!(parse("[1, 2]").is_none());
+    }
+
+    #[test]
+    fn events_map_to_actions() {
+        let json = parse(r#"{"level": "INFO", "event": "puzzle_solved", "puzzle": "safe", "ip": "10.0.0.6"}"#).unwrap();
+        assert_eq!(json.level.as_deref(), Some("INFO"));
+        assert_eq!(json.hits.len(), 1);
+        let hit = &json.hits[0];
+        assert!(matches!(hit.action, Action::MarkSolved));
+        assert_eq!(hit.name.as_deref(), Some("safe"));
+        assert_eq!(hit.ip.as_deref(), Some("10.0.0.6"));
+        assert_eq!(hit.message, "puzzle_solved");
+        assert!(hit.registration.is_none());
+
+        assert!(parse(r#"{"event": "heartbeat"}"#).unwrap().hits.is_empty());
+    }
+
+    #[test]
+    fn display_puts_the_known_fields_first() {
+        let line = r#"{"ts": "12:00:01", "level": "info", "event": "puzzle_solved", "msg": "solved in 3 tries",
+                       "puzzle": "safe", "tries": 3}"#;
+        let json = parse(line).unwrap();
+        assert_eq!(json.display, "12:00:01  INFO      puzzle_solved  solved in 3 tries · puzzle=safe tries=3");
+        assert_eq!(json.hits[0].message, "solved in 3 tries");
+    }
+
+    #[test]
+    fn registration_carries_the_other_fields() {
+        let line = r#"{"ts": "12:00:01", "level": "INFO", "event": "puzzle_registered", "message": "hello",
+                       "puzzle": "laser", "ip": "10.0.0.7", "fw": "2.0", "port": 5001}"#;
+        let json = parse(line).unwrap();
+        let Some(Registration::Fields(fields)) = &json.hits[0].registration else {
+            panic!("expected the registration fields");
+        };
+        let keys: Vec<&str> = fields.keys().map(String::as_str).collect();
+        assert_eq!(keys, ["fw", "ip", "port", "puzzle"]);
+        assert_eq!(fields["port"], 5001);
+    }
+}
@@ -0,0 +1,244 @@
+use crate::logs::LogEntry;
+use anyhow::{anyhow, bail, Result};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Read, Seek, SeekFrom, Write},
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+// Spilled chunks kept in memory for scrolling back and forth around a boundary
+const CACHED_CHUNKS: usize = 3;
+
+// --- Log Buffer ---
+//
+// Keeps the newest `capacity` entries in memory. Older entries are written to a
+// spill file in fixed-size chunks and read back on demand, so indices stay
+// stable and scrolling back works as before while memory stays flat.
+
+pub struct LogBuffer {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+    chunk_size: usize,
+    // Index of `entries[0]`, everything before it was spilled
+    first: usize,
+    spill: Option<SpillFile>,
+    // Set once the spill file failed, older lines are dropped from then on
+    spill_error: Option<String>,
+    cache: RefCell<VecDeque<(usize, Vec<LogEntry>)>>,
+}
+
+struct SpillFile {
+    path: PathBuf,
+    file: File,
+    // Byte offset of every chunk, plus the end of the last one
+    offsets: Vec<u64>,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity + 1),
+            capacity,
+            chunk_size: (capacity / 4).max(1),
+            first: 0,
+            spill: None,
+            spill_error: None,
+            cache: RefCell::new(VecDeque::new()),
+        }
+    }
+
+    // Total number of entries, spilled ones included
+    pub fn len(&self) -> usize {
+        self.first + self.entries.len()
+    }
+
+    // Drops everything, including the spill file
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.first = 0;
+        self.spill = None;
+        self.spill_error = None;
+        self.cache.get_mut().clear();
+    }
+
+    pub fn spill_error(&self) -> Option<&str> {
+        self.spill_error.as_deref()
+    }
+
+    pub fn push(&mut self, entry: LogEntry) {
+        self.entries.push_back(entry);
+        if self.entries.len() > self.capacity {
+            let chunk: Vec<LogEntry> = self.entries.drain(..self.chunk_size).collect();
+            if let Err(e) = self.spill_chunk(&chunk) {
+                self.spill_error = Some(format!("{:#}", e));
+            }
+            self.first += chunk.len();
+        }
+    }
+
+    // `None` for indices that were lost because the spill file failed
+    pub fn get(&self, index: usize) -> Option<Cow<'_, LogEntry>> {
+        if index >= self.first {
+            return self.entries.get(index - self.first).map(Cow::Borrowed);
+        }
+
+        let chunk = index / self.chunk_size;
+        let mut cache = self.cache.borrow_mut();
+        if !cache.iter().any(|(c, _)| *c == chunk) {
+            let entries = self.read_chunk(chunk).ok()?;
+            if cache.len() >= CACHED_CHUNKS {
+                cache.pop_front();
+            }
+            cache.push_back((chunk, entries));
+        }
+        let (_, entries) = cache.iter().find(|(c, _)| *c == chunk)?;
+        entries.get(index % self.chunk_size).cloned().map(Cow::Owned)
+    }
+
+    pub fn is_in_memory(&self, index: usize) -> bool {
+        index >= self.first && index < self.len()
+    }
+
+    // Only entries still in memory can change, e.g. a traceback collecting lines
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut LogEntry> {
+        let offset = index.checked_sub(self.first)?;
+        self.entries.get_mut(offset)
+    }
+
+    // Iterates from `start` to the newest entry, skipping lost ones
+    pub fn iter_from(&self, start: usize) -> impl Iterator<Item = (usize, Cow<'_, LogEntry>)> {
+        (start..self.len()).filter_map(|i| Some((i, self.get(i)?)))
+    }
+
+    fn spill_chunk(&mut self, chunk: &[LogEntry]) -> Result<()> {
+        if self.spill_error.is_some() {
+            return Ok(());
+        }
+        if self.spill.is_none() {
+            self.spill = Some(SpillFile::create()?);
+        }
+        let Some(spill) = &mut self.spill else { return Ok(()) };
+
+        let mut data = Vec::new();
+        for entry in chunk {
+            serde_json::to_writer(&mut data, entry)?;
+            data.push(b'\n');
+        }
+        spill.file.seek(SeekFrom::End(0))?;
+        spill.file.write_all(&data)?;
+        let end = spill.offsets.last().copied().unwrap_or(0) + data.len() as u64;
+        if spill.offsets.is_empty() {
+            spill.offsets.push(0);
+        }
+        spill.offsets.push(end);
+        Ok(())
+    }
+
+    fn read_chunk(&self, chunk: usize) -> Result<Vec<LogEntry>> {
+        let spill = self.spill.as_ref().ok_or_else(|| anyhow!("Nothing spilled"))?;
+        let (Some(&start), Some(&end)) = (spill.offsets.get(chunk), spill.offsets.get(chunk + 1)) else {
+            bail!("Chunk {} was never written", chunk);
+        };
+
+        let mut file = &spill.file;
+        file.seek(SeekFrom::Start(start))?;
+        BufReader::new(file.take(end - start))
+            .lines()
+            .map(|line| Ok(serde_json::from_str(&line?)?))
+            .collect()
+    }
+}
+
+impl SpillFile {
+    fn create() -> Result<Self> {
+        // Numbered, so several buffers in one process do not share a file
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let number = NEXT.fetch_add(1, Ordering::Relaxed);
+        let name = format!("servertui-{}-{}.log.jsonl", std::process::id(), number);
+        let path = std::env::temp_dir().join(name);
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(&path)?;
+        Ok(Self { path, file, offsets: Vec::new() })
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::LogSource;
+
+    fn buffer(capacity: usize, count: usize) -> LogBuffer {
+        let mut logs = LogBuffer::new(capacity);
+        for i in 0..count {
+            logs.push(LogEntry::new(LogSource::Stdout, None, format!("line {}", i)));
+        }
+        logs
+    }
+
+    fn text(logs: &LogBuffer, index: usize) -> Option<String> {
+        logs.get(index).map(|e| e.text.clone())
+    }
+
+    #[test]
+    fn keeps_everything_below_capacity() {
+        let logs = buffer(8, 8);
+        assert_eq!(logs.len(), 8);
+        assert_eq!(logs.first, 0);
+        assert!(logs.spill.is_none());
+        assert_eq!(text(&logs, 7).as_deref(), Some("line 7"));
+        assert_eq!(text(&logs, 8), None);
+    }
+
+    #[test]
+    fn spilled_entries_read_back() {
+        let logs = buffer(8, 50);
+        assert_eq!(logs.len(), 50);
+        assert!(logs.spill_error().is_none());
+        assert!(logs.first > 0);
+        assert!(logs.entries.len() <= 8);
+
+        // Every chunk, also after others pushed it out of the cache
+        for i in (0..50).chain([3, 41, 0]) {
+            assert_eq!(text(&logs, i), Some(format!("line {}", i)));
+        }
+    }
+
+    #[test]
+    fn iter_from_crosses_into_memory() {
+        let logs = buffer(8, 30);
+        let start = logs.first - 3;
+        let texts: Vec<(usize, String)> = logs.iter_from(start).map(|(i, e)| (i, e.text.clone())).collect();
+        assert_eq!(texts.len(), 30 - start);
+        for (i, text) in texts {
+            assert_eq!(text, format!("line {}", i));
+        }
+        assert_eq!(logs.iter_from(30).count(), 0);
+    }
+
+    #[test]
+    fn only_memory_entries_change() {
+        let mut logs = buffer(4, 10);
+        assert!(logs.get_mut(0).is_none());
+        logs.get_mut(9).unwrap().text = "changed".to_string();
+        assert_eq!(text(&logs, 9).as_deref(), Some("changed"));
+    }
+
+    #[test]
+    fn clear_removes_the_spill_file() {
+        let mut logs = buffer(4, 10);
+        let path = logs.spill.as_ref().unwrap().path.clone();
+        assert!(path.exists());
+        logs.clear();
+        assert_eq!(logs.len(), 0);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,339 @@
+use crate::timebase;
+use chrono::{DateTime, Local};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+// --- Log Records ---
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warning,
+    Error,
+    Critical,
+}
+
+impl LogLevel {
+    // Level names as servers print them, also accepted in the filter bar
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "DEBUG" => Some(LogLevel::Debug),
+            "INFO" => Some(LogLevel::Info),
+            "WARN" | "WARNING" => Some(LogLevel::Warning),
+            "ERROR" => Some(LogLevel::Error),
+            "CRITICAL" | "FATAL" => Some(LogLevel::Critical),
+            _ => None,
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            LogLevel::Debug => Color::DarkGray,
+            LogLevel::Info => Color::Gray,
+            LogLevel::Warning => Color::Yellow,
+            LogLevel::Error => Color::Red,
+            LogLevel::Critical => Color::Magenta,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogSource {
+    Stdout,
+    Stderr,
+    Supervisor,
+    Operator,
+    Monitor,
+    Rules,
+    Alert,
+}
+
+impl LogSource {
+    // Prefix in the log pane, server stdout stays unmarked like before
+    pub fn tag(&self) -> Option<&'static str> {
+        match self {
+            LogSource::Stdout => None,
+            LogSource::Stderr => Some("STDERR"),
+            LogSource::Supervisor => Some("SUPERVISOR"),
+            LogSource::Operator => Some("OPERATOR"),
+            LogSource::Monitor => Some("MONITOR"),
+            LogSource::Rules => Some("RULES"),
+            LogSource::Alert => Some("ALERT"),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "stdout" => Some(LogSource::Stdout),
+            "stderr" => Some(LogSource::Stderr),
+            "supervisor" => Some(LogSource::Supervisor),
+            "operator" => Some(LogSource::Operator),
+            "monitor" => Some(LogSource::Monitor),
+            "rules" => Some(LogSource::Rules),
+            "alert" => Some(LogSource::Alert),
+            _ => None,
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            LogSource::Stdout | LogSource::Stderr => Color::DarkGray,
+            LogSource::Supervisor | LogSource::Rules => Color::Cyan,
+            LogSource::Operator => Color::LightBlue,
+            LogSource::Monitor | LogSource::Alert => Color::LightRed,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Local>,
+    pub source: LogSource,
+    pub level: Option<LogLevel>,
+    pub text: String,
+    // Further lines of a Python traceback, empty for normal entries
+    pub traceback: Vec<String>,
+}
+
+impl LogEntry {
+    pub fn new(source: LogSource, level: Option<LogLevel>, text: String) -> Self {
+        Self {
+            timestamp: timebase::local_now(),
+            source,
+            level,
+            text,
+            traceback: Vec::new(),
+        }
+    }
+
+    pub fn is_traceback(&self) -> bool {
+        self.text.starts_with(TRACEBACK_START)
+    }
+
+    // The entry text followed by any traceback lines
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.text.as_str()).chain(self.traceback.iter().map(String::as_str))
+    }
+
+    pub fn contains(&self, regex: &Regex) -> bool {
+        self.lines().any(|l| regex.is_match(l))
+    }
+
+    // Number of rows the entry takes in the log pane
+    pub fn height(&self, expanded: bool) -> usize {
+        if expanded { 1 + self.traceback.len() } else { 1 }
+    }
+
+    // `search` highlights matches, `current` marks the entry the search jumped to
+    pub fn render(&self, expanded: bool, search: Option<&Regex>, current: bool) -> Vec<Line<'static>> {
+        let color = self.level.map_or(Color::Gray, |l| l.color());
+        let time_style = if current {
+            Style::default().fg(Color::Black).bg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let mut head = vec![Span::styled(format!("{} ", self.timestamp.format("%H:%M:%S")), time_style)];
+        if let Some(tag) = self.source.tag() {
+            head.push(Span::styled(format!("[{}] ", tag), Style::default().fg(self.source.color())));
+        }
+
+        if !self.is_traceback() {
+            head.extend(highlight(self.text.clone(), Style::default().fg(color), search));
+            return vec![Line::from(head)];
+        }
+
+        let style = Style::default().fg(color).add_modifier(Modifier::BOLD);
+        if !expanded {
+            // Collapsed: the (last) exception line is what matters
+            let summary = self.traceback.iter().rev().map(|l| l.trim()).find(|l| !l.is_empty()).unwrap_or("");
+            head.extend(highlight(format!("▶ Traceback: {}", summary), style, search));
+            head.push(Span::styled(
+                format!(" (+{} lines, e to expand)", self.traceback.len()),
+                Style::default().fg(Color::DarkGray),
+            ));
+            return vec![Line::from(head)];
+        }
+
+        head.extend(highlight(format!("▼ {}", self.text), style, search));
+        let mut lines = vec![Line::from(head)];
+        lines.extend(self.traceback.iter().map(|l| {
+            Line::from(highlight(format!("         {}", l), Style::default().fg(color), search))
+        }));
+        lines
+    }
+}
+
+// Splits text into spans with every search match drawn black on yellow
+fn highlight(text: String, style: Style, search: Option<&Regex>) -> Vec<Span<'static>> {
+    let Some(regex) = search else {
+        return vec![Span::styled(text, style)];
+    };
+
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for m in regex.find_iter(&text).filter(|m| !m.is_empty()) {
+        if m.start() > last {
+            spans.push(Span::styled(text[last..m.start()].to_string(), style));
+        }
+        spans.push(Span::styled(m.as_str().to_string(), style.fg(Color::Black).bg(Color::Yellow)));
+        last = m.end();
+    }
+    if last < text.len() {
+        spans.push(Span::styled(text[last..].to_string(), style));
+    }
+    spans
+}
+
+// --- Tracebacks ---
+
+pub const TRACEBACK_START: &str = "Traceback (most recent call last):";
+
+// Python prints chained exceptions as several tracebacks joined by one of these
+const CHAIN_LINES: [&str; 2] = [
+    "During handling of the above exception, another exception occurred:",
+    "The above exception was the direct cause of the following exception:",
+];
+
+// Where an open traceback is while it collects lines
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TracebackState {
+    // Indented frames up to the unindented exception line
+    Frames,
+    // After the exception line, only a chained exception continues it
+    Ended,
+    // After a chain line, waiting for the next "Traceback" line
+    Chained,
+}
+
+impl TracebackState {
+    // The state after `line` if the line belongs to the traceback, `None` if it starts a new entry
+    pub fn next(self, line: &str) -> Option<TracebackState> {
+        let blank = line.trim().is_empty();
+        match self {
+            TracebackState::Frames if blank || line.starts_with(TRACEBACK_START) => None,
+            TracebackState::Frames if line.starts_with(char::is_whitespace) => Some(TracebackState::Frames),
+            TracebackState::Frames => Some(TracebackState::Ended),
+            TracebackState::Ended | TracebackState::Chained if blank => Some(self),
+            TracebackState::Ended if CHAIN_LINES.contains(&line.trim()) => Some(TracebackState::Chained),
+            TracebackState::Chained if line.starts_with(TRACEBACK_START) => Some(TracebackState::Frames),
+            _ => None,
+        }
+    }
+}
+
+// --- Level Detection ---
+
+static LEVEL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(DEBUG|INFO|WARN|WARNING|ERROR|CRITICAL|FATAL)\b").unwrap()
+});
+
+// HTTP access lines carry no level, but the status code says enough
+static HTTP_STATUS_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"HTTP/[\d.]+" (\d{3})\b"#).unwrap());
+
+pub fn detect_level(line: &str) -> Option<LogLevel> {
+    if let Some(caps) = LEVEL_REGEX.captures(line) {
+        return LogLevel::from_name(&caps[1]);
+    }
+
+    let status = HTTP_STATUS_REGEX.captures(line)?;
+    Some(match status[1].as_bytes()[0] {
+        b'5' => LogLevel::Error,
+        b'4' => LogLevel::Warning,
+        _ => LogLevel::Info,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Feeds the lines after a "Traceback" line, returning how many of them joined it
+    fn collected(lines: &[&str]) -> usize {
+        let mut state = TracebackState::Frames;
+        for (i, line) in lines.iter().enumerate() {
+            match state.next(line) {
+                Some(next) => state = next,
+                None => return i,
+            }
+        }
+        lines.len()
+    }
+
+    #[test]
+    fn levels_are_found_as_words() {
+        assert_eq!(detect_level("2026-10-16 12:00:01 INFO Server ready"), Some(LogLevel::Info));
+        assert_eq!(detect_level("DEBUG: tick"), Some(LogLevel::Debug));
+        assert_eq!(detect_level("[WARN] slow puzzle"), Some(LogLevel::Warning));
+        assert_eq!(detect_level("WARNING:root:slow puzzle"), Some(LogLevel::Warning));
+        assert_eq!(detect_level("ERROR puzzle safe failed"), Some(LogLevel::Error));
+        assert_eq!(detect_level("FATAL out of memory"), Some(LogLevel::Critical));
+        assert_eq!(detect_level("CRITICAL lost the door"), Some(LogLevel::Critical));
+        assert_eq!(detect_level("INFORMATION is not a level"), None);
+        assert_eq!(detect_level("Puzzle safe solved"), None);
+    }
+
+    #[test]
+    fn http_status_gives_the_level() {
+        let line = |status| format!(r#"10.0.0.20 - - [16/Oct/2026 15:19:08] "GET /state HTTP/1.1" {} -"#, status);
+        assert_eq!(detect_level(&line(200)), Some(LogLevel::Info));
+        assert_eq!(detect_level(&line(304)), Some(LogLevel::Info));
+        assert_eq!(detect_level(&line(404)), Some(LogLevel::Warning));
+        assert_eq!(detect_level(&line(500)), Some(LogLevel::Error));
+        // A level word wins over the status
+        assert_eq!(detect_level(&format!("ERROR {}", line(200))), Some(LogLevel::Error));
+    }
+
+    #[test]
+    fn traceback_ends_after_the_exception() {
+        let lines = [
+            r#"  File "server.py", line 12, in handle"#,
+            "    raise ValueError(x)",
+            "ValueError: bad code",
+            "INFO next request",
+        ];
+        assert_eq!(collected(&lines), 3);
+        assert_eq!(collected(&[r#"  File "server.py", line 12"#, ""]), 1);
+        assert_eq!(collected(&[r#"  File "server.py", line 12"#, TRACEBACK_START]), 1);
+    }
+
+    #[test]
+    fn chained_exceptions_stay_in_one_traceback() {
+        let lines = [
+            r#"  File "server.py", line 12, in handle"#,
+            "KeyError: 'safe'",
+            "",
+            "During handling of the above exception, another exception occurred:",
+            "",
+            TRACEBACK_START,
+            r#"  File "server.py", line 14, in handle"#,
+            "RuntimeError: unknown puzzle",
+            "",
+            "The above exception was the direct cause of the following exception:",
+            "",
+            TRACEBACK_START,
+            r#"  File "server.py", line 30, in main"#,
+            "SystemExit: 1",
+            "INFO shutting down",
+        ];
+        assert_eq!(collected(&lines), 14);
+    }
+
+    #[test]
+    fn chain_line_needs_a_traceback_after_it() {
+        let lines = [
+            "KeyError: 'safe'",
+            "During handling of the above exception, another exception occurred:",
+            "INFO not a traceback",
+        ];
+        assert_eq!(collected(&lines), 2);
+        assert_eq!(collected(&["KeyError: 'safe'", "ValueError: not chained"]), 1);
+    }
+}
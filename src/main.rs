@@ -1,7 +1,30 @@
+mod client;
+mod clock;
+mod config;
+mod filter;
+mod game;
+mod hints;
+mod jsonlog;
+mod logbuffer;
+mod logs;
+mod metrics;
+mod mouse;
+mod probe;
+mod procmon;
+mod puzzle;
+mod pyliteral;
+mod recording;
+mod rules;
+mod sessionlog;
+mod stats;
+mod supervisor;
+mod tabs;
+mod timebase;
+
 use anyhow::Result;
 use chrono::{DateTime, Local};
 use crossterm::{
-    event::{self, Event, KeyCode, KeyModifiers},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -9,26 +32,102 @@ use local_ip_address::local_ip;
 use ratatui::{
     layout::Margin,
     prelude::*,
-    widgets::{Block, Borders, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    widgets::{
+        Axis, Block, Borders, Chart, Clear, Dataset, GraphType, LegendPosition, List, ListItem, ListState, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+        Sparkline, Tabs, Wrap,
+    },
 };
 use regex::Regex;
 use std::{
-    collections::{HashMap, HashSet},
-    io::{self, BufRead, BufReader},
-    process::{Child, Command, Stdio},
+    collections::{BTreeMap, HashMap},
+    io,
+    path::PathBuf,
     sync::{Arc, Mutex},
-    thread,
     time::Duration,
 };
+use client::{Client, ClientSort};
+use clock::{format_game_time, GameClock};
+use config::{Config, ProcessConfig};
+use filter::{search_regex, LogFilter, LogView};
+use game::{parse_team, Game, GameRecord, GameResult, History};
+use hints::{GivenHint, HintCatalogue, HintPanel};
+use logbuffer::LogBuffer;
+use logs::{detect_level, LogEntry, LogLevel, LogSource, TracebackState, TRACEBACK_START};
+use metrics::{Metrics, Sample};
+use mouse::{ListRegion, Regions};
+use procmon::{format_uptime, ProcessMonitor, ProcessStats, Warnings};
+use puzzle::{format_age, Liveness, Puzzle, PuzzleState};
+use recording::{ClockAction, Recorder, Replay};
+use rules::{Action, ClientKind, Hit, Rules};
+use sessionlog::SessionLog;
+use stats::{Stats, SuccessRate};
+use supervisor::{ServerAction, ServerStatus, Supervisor};
+use tabs::{Tab, UiState};
 use sysinfo::{CpuRefreshKind, MemoryRefreshKind,RefreshKind, System};
 
 // --- Data Structures ---
 
-struct Puzzle {
+// Actions that need a [y] before they run
+#[derive(Clone, Copy)]
+enum Confirm {
+    Server(ServerAction),
+    ResetClock,
+    EndGame,
+}
+
+impl Confirm {
+    fn prompt(&self) -> &'static str {
+        match self {
+            Confirm::Server(action) => action.prompt(),
+            Confirm::ResetClock => "Reset the game clock?",
+            Confirm::EndGame => "End the game?",
+        }
+    }
+
+    // Shown as buttons, any other key cancels as well
+    fn buttons(&self) -> &'static [(&'static str, KeyCode)] {
+        match self {
+            Confirm::EndGame => &[
+                ("[e] escaped", KeyCode::Char('e')),
+                ("[f] failed", KeyCode::Char('f')),
+                ("[Esc] cancel", KeyCode::Esc),
+            ],
+            _ => &[("[y] confirm", KeyCode::Char('y')), ("[Esc] cancel", KeyCode::Esc)],
+        }
+    }
+}
+
+// Text input at the bottom of the log pane
+#[derive(Clone, Copy, PartialEq)]
+enum PromptKind {
+    Search,
+    Filter,
+    Command,
+    NewGame,
+}
+
+struct Prompt {
+    kind: PromptKind,
+    input: String,
+}
+
+// Detail pane of the selected puzzle on the puzzles tab, with its own view of
+// the log lines mentioning it
+struct PuzzleDetail {
     name: String,
-    ip: String,
-    #[allow(dead_code)]
-    last_seen: DateTime<Local>,
+    filter: LogFilter,
+    log_view: LogView,
+    // Entries hidden below the shown ones, 0 follows the newest
+    log_offset: usize,
+    log_height: usize,
+}
+
+impl PuzzleDetail {
+    fn new(name: String) -> Self {
+        // `puzzle:` terms only fail without a name, which a known puzzle always has
+        let filter = LogFilter::parse(&format!("puzzle:{}", name)).unwrap_or_default();
+        Self { name, filter, log_view: LogView::default(), log_offset: 0, log_height: 10 }
+    }
 }
 
 struct App {
@@ -39,193 +138,982 @@ struct App {
     uptime: u64,
     ip_address: String,
     hostname: String,
+    // The server and its children, `None` while it is not running
+    server_process: Option<ProcessStats>,
+    process_config: ProcessConfig,
+    metrics: Metrics,
 
     // App Data
-    logs: Vec<String>,
+    logs: LogBuffer,
+    // Index of a traceback still collecting lines, per stream (stdout, stderr)
+    open_tracebacks: [Option<(usize, TracebackState)>; 2],
+    // Oldest entry changed after it was stored, the view checks it again
+    logs_changed: Option<usize>,
+    session_log: Option<SessionLog>,
+    recorder: Option<Recorder>,
     puzzles: HashMap<String, Puzzle>,
-    clients: HashSet<String>,
+    rules: Arc<Rules>,
+    json_lines: bool,
+    puzzle_stale_after: Duration,
+    puzzle_offline_after: Duration,
+    puzzle_columns: Vec<String>,
+    clients: HashMap<String, Client>,
+    client_timeout: Duration,
     
     // Status
-    server_ready: bool,
+    server_status: ServerStatus,
+    clock: GameClock,
+    fields: BTreeMap<String, String>,
+    alert: Option<String>,
+    // Position and speed while replaying a recording
+    replay_status: Option<String>,
+    hints: HintCatalogue,
+    // Hints given since the clock was last reset
+    hints_given: Vec<GivenHint>,
+    game: Option<Game>,
+    history: History,
 
     // UI State
+    tab: Tab,
+    // Where the active tab is remembered, `None` while replaying or after saving it failed
+    state_file: Option<PathBuf>,
+    // Positions below index into `log_view`, the entries passing the filter
+    log_view: LogView,
+    // First visible log entry, only used while not following the tail
     scroll_position: usize,
+    follow_logs: bool,
+    expand_tracebacks: bool,
+    // Size and shown range of the log pane as last drawn, for scrolling
+    log_view_height: usize,
+    log_view_start: usize,
+    log_view_end: usize,
+    filter: LogFilter,
+    search: Option<Regex>,
+    search_text: String,
+    // Log index of the match n/N last jumped to
+    search_match: Option<usize>,
+    prompt: Option<Prompt>,
+    prompt_error: Option<String>,
+    command_history: Vec<String>,
+    // Entry of `command_history` shown while browsing it with ↑/↓
+    history_position: Option<usize>,
+    // Typed into the command bar, sent once the app lock is released
+    pending_command: Option<String>,
+    hint_panel: Option<HintPanel>,
+    // Selected game in the history on the game tab, newest first
+    history_selected: usize,
+    // Computed from the history while the statistics are shown
+    stats: Option<Stats>,
+    // Picked in the hint panel, delivered once the app lock is released
+    pending_hint: Option<(String, String)>,
+    pending_confirm: Option<Confirm>,
+    client_sort: ClientSort,
+    // Selected on the puzzles tab, kept by name since new puzzles shift the list
+    puzzle_selected: Option<String>,
+    puzzle_detail: Option<PuzzleDetail>,
+    // Clicked in the client list, by IP
+    client_selected: Option<String>,
+    // Where the last frame drew what, for the mouse
+    regions: Regions,
+    dragging_scrollbar: bool,
     should_quit: bool,
 }
 
 impl App {
-    fn new() -> Self {
+    fn new(config: &Config) -> Result<Self> {
         let ip = local_ip().map(|ip| ip.to_string()).unwrap_or_else(|_| "Unknown".to_string());
         let hostname = System::host_name().unwrap_or_else(|| "Unknown".to_string());
 
-        Self {
+        let mut app = Self {
             cpu_usage: 0.0,
             ram_usage: 0,
             total_ram: 0,
             uptime: 0,
             ip_address: ip,
             hostname,
-            logs: Vec::new(),
+            server_process: None,
+            process_config: config.process.clone(),
+            metrics: Metrics::new(&config.metrics),
+            logs: LogBuffer::new(config.logs.memory_lines),
+            open_tracebacks: [None, None],
+            logs_changed: None,
+            session_log: config.logs.save.then(|| SessionLog::open(&config.logs)).transpose()?,
+            recorder: config.recording.enabled.then(|| Recorder::create(&config.recording.dir)).transpose()?,
             puzzles: HashMap::new(),
-            clients: HashSet::new(),
-            server_ready: false, 
+            rules: Arc::new(Rules::load(config.rules.file.as_deref())?),
+            json_lines: config.rules.json,
+            puzzle_stale_after: Duration::from_secs(config.puzzles.stale_secs),
+            puzzle_offline_after: Duration::from_secs(config.puzzles.offline_secs),
+            puzzle_columns: config.puzzles.columns.clone(),
+            clients: HashMap::new(),
+            client_timeout: Duration::from_secs(config.clients.timeout_secs),
+            server_status: ServerStatus::Starting,
+            clock: GameClock::new(config.game.clone()),
+            fields: BTreeMap::new(),
+            alert: None,
+            replay_status: None,
+            hints: HintCatalogue::load(config.hints.file.as_deref())?,
+            hints_given: Vec::new(),
+            game: None,
+            // A replay only shows the games of the recording, not the live history
+            history: match config.replay {
+                Some(_) => History::default(),
+                None => History::load(&config.history.file, config.history.save)?,
+            },
+            tab: UiState::load(&config.ui.state_file).tab,
+            // A replay opens on the saved tab but leaves the file alone
+            state_file: config.replay.is_none().then(|| config.ui.state_file.clone()),
+            log_view: LogView::default(),
             scroll_position: 0,
+            follow_logs: true,
+            expand_tracebacks: false,
+            log_view_height: 10,
+            log_view_start: 0,
+            log_view_end: 0,
+            filter: LogFilter::default(),
+            search: None,
+            search_text: String::new(),
+            search_match: None,
+            prompt: None,
+            prompt_error: None,
+            command_history: Vec::new(),
+            history_position: None,
+            pending_command: None,
+            hint_panel: None,
+            history_selected: 0,
+            stats: None,
+            pending_hint: None,
+            pending_confirm: None,
+            client_sort: ClientSort::LastSeen,
+            puzzle_selected: None,
+            puzzle_detail: None,
+            client_selected: None,
+            regions: Regions::default(),
+            dragging_scrollbar: false,
             should_quit: false,
+        };
+        if app.history.skipped > 0 {
+            let message = format!("Skipped {} unreadable lines in the game history", app.history.skipped);
+            app.log_marked(LogSource::Supervisor, LogLevel::Warning, message);
         }
+        Ok(app)
     }
 
     // Unified function to handle logs from both stdout and stderr
     fn process_log(&mut self, raw_line: String, is_stderr: bool) {
-        // 1. Check Regexes (on the raw line)
-        
-        // Status check
-        if raw_line.contains("Serving at port 8080") {
-            self.server_ready = true;
+        self.record(recording::Event::Line { stderr: is_stderr, text: raw_line.clone() });
+
+        // 1. Structured JSON lines map directly, plain lines go through the rules
+        let json = self.json_lines.then(|| jsonlog::parse(&raw_line)).flatten();
+        let (hits, text, level) = match json {
+            Some(json) => {
+                let level = json.level.and_then(|l| detect_level(&l.to_uppercase()));
+                (json.hits, json.display, level)
+            }
+            None => {
+                let level = detect_level(&raw_line);
+                (self.rules.clone().evaluate(&raw_line), raw_line.clone(), level)
+            }
+        };
+        for hit in hits {
+            self.apply_hit(hit);
         }
 
-        // Puzzle Dict: {'name': 'patchpanel', ... 'ip': '127.0.0.1'}
-        // Re-compiled here for simplicity, or could be static/lazy_static
-        let puzzle_dict_regex = Regex::new(r"\{'name':\s*'([^']+)',.*?'ip':\s*'([^']+)'").unwrap();
-        
-        // Puzzle Registration fallback
-        let puzzle_reg_regex = Regex::new(r"Registering new puzzle\s+(\w+)").unwrap();
-        
-        // HTTP Client: 172.25.208.1 - - [Date]
-        let http_client_regex = Regex::new(r"^(\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3})\s+-\s+-").unwrap();
-        
-        // UDP Client
-        let msg_client_regex = Regex::new(r"Received message from \('(\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3})',").unwrap();
+        // Any mention keeps a puzzle alive
+        for puzzle in self.puzzles.values_mut() {
+            if puzzle.is_mentioned(&raw_line) {
+                puzzle.last_seen = timebase::local_now();
+            }
+        }
+
+        // 2. Store Log
+        let source = if is_stderr { LogSource::Stderr } else { LogSource::Stdout };
+        self.store_line(source, text, level);
+    }
+
+    // Groups Python tracebacks into one entry: everything indented after the
+    // "Traceback" line belongs to it, the first unindented line is the exception.
+    // Chained exceptions that follow it are added to the same entry.
+    fn store_line(&mut self, source: LogSource, text: String, level: Option<LogLevel>) {
+        let stream = if source == LogSource::Stderr { 1 } else { 0 };
 
-        // -- Parsing Logic --
-        if let Some(caps) = puzzle_dict_regex.captures(&raw_line) {
-            let name = caps.get(1).map_or("?", |m| m.as_str()).to_string();
-            let ip = caps.get(2).map_or("?", |m| m.as_str()).to_string();
-            self.puzzles.insert(name.clone(), Puzzle { name, ip, last_seen: Local::now() });
-        } else if let Some(caps) = puzzle_reg_regex.captures(&raw_line) {
-            let name = caps.get(1).map_or("?", |m| m.as_str()).to_string();
-            self.puzzles.entry(name.clone()).or_insert(Puzzle {
-                name,
-                ip: "Waiting...".to_string(),
-                last_seen: Local::now()
-            });
+        // A traceback that was already spilled to disk cannot grow any more
+        if let Some((index, state)) = self.open_tracebacks[stream]
+            && self.logs.is_in_memory(index)
+        {
+            self.open_tracebacks[stream] = state.next(&text).map(|state| (index, state));
+            if self.open_tracebacks[stream].is_some() {
+                self.write_session_log(timebase::local_now(), source, &text);
+                if let Some(entry) = self.logs.get_mut(index) {
+                    entry.traceback.push(text);
+                }
+                self.mark_changed(index);
+                return;
+            }
         }
 
-        if let Some(caps) = http_client_regex.captures(&raw_line) {
-            if let Some(ip) = caps.get(1) {
-                self.clients.insert(ip.as_str().to_string());
+        if text.starts_with(TRACEBACK_START) {
+            self.open_tracebacks[stream] = Some((self.logs.len(), TracebackState::Frames));
+            self.push_log(LogEntry::new(source, Some(LogLevel::Error), text));
+        } else {
+            self.push_log(LogEntry::new(source, level, text));
+        }
+    }
+
+    fn apply_hit(&mut self, hit: Hit) {
+        let Hit { action, name, ip, path, value, message, registration } = hit;
+
+        match action {
+            Action::RegisterPuzzle => {
+                let Some(name) = name else { return };
+                // Re-registration only updates the IP and attributes, the puzzle keeps its state
+                let puzzle = self.puzzle_entry(name.clone());
+                if let Some(ip) = ip {
+                    puzzle.ip = ip;
+                }
+                puzzle.registered_at = Some(timebase::local_now());
+                if let Some(registration) = registration
+                    && let Err(e) = puzzle.read_registration(registration)
+                {
+                    let message = format!("Could not read the registration of {}: {:#}", name, e);
+                    self.log_marked(LogSource::Rules, LogLevel::Warning, message);
+                }
             }
-        } else if let Some(caps) = msg_client_regex.captures(&raw_line) {
-            if let Some(ip) = caps.get(1) {
-                self.clients.insert(ip.as_str().to_string());
+            Action::UpdatePuzzleIp => {
+                if let (Some(name), Some(ip)) = (name, ip) {
+                    self.puzzle_entry(name).ip = ip;
+                }
+            }
+            Action::RegisterClient { kind } => {
+                let Some(ip) = ip else { return };
+                let client = self.client_entry(&ip);
+                match kind {
+                    ClientKind::Http => client.record_http(path),
+                    ClientKind::Udp => client.record_udp(),
+                }
+            }
+            Action::MarkServerReady => self.server_status = ServerStatus::Online,
+            Action::RaiseAlert { .. } => {
+                self.log_marked(LogSource::Alert, LogLevel::Error, message.clone());
+                self.alert = Some(format!("{} {}", timebase::local_now().format("%H:%M:%S"), message));
+            }
+            Action::SetField { field } => {
+                let Some(value) = value else { return };
+                match name {
+                    Some(name) => {
+                        self.puzzle_entry(name).fields.insert(field, value);
+                    }
+                    None => {
+                        self.fields.insert(field, value);
+                    }
+                }
+            }
+            Action::MarkActive | Action::MarkSolved | Action::MarkReset | Action::MarkError => {
+                let (Some(name), Some(state)) = (name, action.puzzle_state()) else { return };
+                let game_time = (!self.clock.is_idle()).then(|| self.clock.elapsed());
+                let puzzle = self.puzzle_entry(name.clone());
+                let newly_solved = state == PuzzleState::Solved && puzzle.state != PuzzleState::Solved;
+                puzzle.set_state(state, game_time);
+                if newly_solved
+                    && let (Some(game), Some(game_time)) = (&mut self.game, game_time)
+                {
+                    game.puzzle_solved(&name, game_time);
+                }
             }
         }
+    }
+
+    fn puzzle_entry(&mut self, name: String) -> &mut Puzzle {
+        self.puzzles.entry(name.clone())
+            .or_insert_with(|| Puzzle::new(name, "Waiting...".to_string()))
+    }
+
+    fn client_entry(&mut self, ip: &str) -> &mut Client {
+        self.clients.entry(ip.to_string()).or_insert_with(|| Client::new(ip.to_string()))
+    }
+
+    // Supervisor and operator events go into the same log pane, clearly marked
+    fn log_event(&mut self, message: String) {
+        self.log_marked(LogSource::Supervisor, LogLevel::Info, message);
+    }
 
-        // 2. Store Log (Add prefix if stderr)
-        let display_line = if is_stderr {
-            format!("[STDERR] {}", raw_line)
+    fn log_operator(&mut self, message: String) {
+        self.log_marked(LogSource::Operator, LogLevel::Info, message);
+    }
+
+    fn log_marked(&mut self, source: LogSource, level: LogLevel, message: String) {
+        // Keep one entry per line, error chains (e.g. regex errors) can span several
+        let message = message.lines().map(str::trim).filter(|l| !l.is_empty()).collect::<Vec<_>>().join(" ");
+
+        // Monitor, rule and alert entries follow from the recorded lines on replay
+        if matches!(source, LogSource::Supervisor | LogSource::Operator) {
+            self.record(recording::Event::Log { source, level, message: message.clone() });
+        }
+        self.push_log(LogEntry::new(source, Some(level), message));
+    }
+
+    // A failing recording is reported once and then given up, like the log file
+    fn record(&mut self, event: recording::Event) {
+        let Some(recorder) = &mut self.recorder else { return };
+        if let Err(e) = recorder.record(event) {
+            self.recorder = None;
+            let message = format!("Recording disabled: {:#}", e);
+            self.push_log(LogEntry::new(LogSource::Supervisor, Some(LogLevel::Error), message));
+        }
+    }
+
+    fn replay_event(&mut self, event: recording::Event) {
+        match event {
+            recording::Event::Line { stderr, text } => self.process_log(text, stderr),
+            recording::Event::Clock { action } => self.apply_clock(action),
+            recording::Event::Hint { puzzle, text } => self.apply_hint(puzzle, text),
+            recording::Event::GameStart { team, players } => self.apply_game_start(team, players),
+            recording::Event::GameEnd { result } => self.apply_game_end(result),
+            recording::Event::Sample(sample) => self.metrics.push(sample),
+            recording::Event::Log { source, level, message } => self.log_marked(source, level, message),
+        }
+    }
+
+    // Back to an empty room, used when a replay seeks backwards. UI settings stay.
+    fn clear_state(&mut self) {
+        self.logs.clear();
+        self.open_tracebacks = [None, None];
+        self.logs_changed = None;
+        self.puzzles.clear();
+        self.clients.clear();
+        self.server_status = ServerStatus::Starting;
+        self.clock.reset();
+        self.fields.clear();
+        self.alert = None;
+        self.hints_given.clear();
+        self.game = None;
+        // Replays start without history, the recorded games are ended again
+        self.history = History::default();
+        self.history_selected = 0;
+        self.metrics.clear();
+        self.log_view.invalidate();
+        if let Some(detail) = &mut self.puzzle_detail {
+            detail.log_view.invalidate();
+        }
+        self.search_match = None;
+        self.follow_logs = true;
+    }
+
+    fn push_log(&mut self, entry: LogEntry) {
+        self.write_session_log(entry.timestamp, entry.source, &entry.text);
+        let had_error = self.logs.spill_error().is_some();
+        self.logs.push(entry);
+
+        // Reported once, straight into the buffer so this cannot recurse
+        if !had_error && let Some(error) = self.logs.spill_error() {
+            let message = format!("Log spill file failed, older lines are dropped: {}", error);
+            self.logs.push(LogEntry::new(LogSource::Supervisor, Some(LogLevel::Error), message));
+        }
+    }
+
+    // A failing log file is reported once and then given up
+    fn write_session_log(&mut self, timestamp: DateTime<Local>, source: LogSource, text: &str) {
+        let Some(log) = &mut self.session_log else { return };
+        if let Err(e) = log.write(timestamp, source, text) {
+            self.session_log = None;
+            let message = format!("Log file disabled: {:#}", e);
+            self.logs.push(LogEntry::new(LogSource::Supervisor, Some(LogLevel::Error), message));
+        }
+    }
+
+    fn mark_changed(&mut self, index: usize) {
+        self.logs_changed = Some(self.logs_changed.map_or(index, |c| c.min(index)));
+    }
+
+    // Called every frame while live, takes a sample once per interval
+    fn sample_metrics(&mut self) {
+        let Some(log_lines_per_sec) = self.metrics.due(self.logs.len()) else { return };
+        let sample = Sample {
+            cpu: self.cpu_usage,
+            ram_mb: self.ram_usage,
+            server_cpu: self.server_process.map(|p| p.cpu),
+            server_rss_mb: self.server_process.map(|p| p.rss_mb()),
+            log_lines_per_sec,
+        };
+        self.record(recording::Event::Sample(sample));
+        self.metrics.push(sample);
+    }
+
+    // Called every frame, logs puzzles going offline and coming back once
+    fn check_puzzles(&mut self) {
+        let mut events = Vec::new();
+        for puzzle in self.puzzles.values_mut() {
+            let offline = puzzle.liveness(self.puzzle_stale_after, self.puzzle_offline_after) == Liveness::Offline;
+            if offline && !puzzle.reported_offline {
+                events.push((
+                    LogLevel::Warning,
+                    format!("Puzzle {} is OFFLINE (last seen {})", puzzle.name, format_age(puzzle.age())),
+                ));
+            } else if !offline && puzzle.reported_offline {
+                events.push((LogLevel::Info, format!("Puzzle {} is back online", puzzle.name)));
+            }
+            puzzle.reported_offline = offline;
+        }
+        for (level, event) in events {
+            self.log_marked(LogSource::Monitor, level, event);
+        }
+    }
+
+    // Start, pause and resume share one key
+    fn toggle_clock(&mut self) {
+        if self.clock.is_idle() {
+            self.apply_clock(ClockAction::Start);
+            self.log_operator("Game clock started".to_string());
+        } else if self.clock.is_running() {
+            self.apply_clock(ClockAction::Pause);
+            self.log_operator(format!("Game clock paused at {}", self.clock.display()));
+        } else {
+            self.apply_clock(ClockAction::Resume);
+            self.log_operator(format!("Game clock resumed at {}", self.clock.display()));
+        }
+    }
+
+    // Resetting would take the hints and time from the running game, it is ended with G instead
+    fn confirm_reset_clock(&mut self) {
+        if self.game.is_some() {
+            let message = "A game is running, end it with G first".to_string();
+            self.log_marked(LogSource::Operator, LogLevel::Warning, message);
+        } else {
+            self.pending_confirm = Some(Confirm::ResetClock);
+        }
+    }
+
+    fn reset_clock(&mut self) {
+        self.apply_clock(ClockAction::Reset);
+        self.log_operator("Game clock reset".to_string());
+    }
+
+    fn apply_clock(&mut self, action: ClockAction) {
+        self.record(recording::Event::Clock { action });
+        match action {
+            ClockAction::Start => self.clock.start(),
+            ClockAction::Pause => self.clock.pause(),
+            ClockAction::Resume => self.clock.resume(),
+            ClockAction::Reset => {
+                self.clock.reset();
+                self.hints_given.clear();
+            }
+        }
+    }
+
+    // --- Game Sessions ---
+
+    fn start_game(&mut self, team: String, players: u32) {
+        self.apply_game_start(team.clone(), players);
+        self.log_operator(format!("Game started: {} ({} players)", team, players));
+    }
+
+    // A new game starts the clock from zero and forgets the hints of the last one
+    fn apply_game_start(&mut self, team: String, players: u32) {
+        self.record(recording::Event::GameStart { team: team.clone(), players });
+        self.clock.reset();
+        self.clock.start();
+        self.hints_given.clear();
+        self.game = Some(Game::new(team, players, timebase::local_now()));
+    }
+
+    fn end_game(&mut self, result: GameResult) {
+        let Some(game) = &self.game else { return };
+        let (team, solved) = (game.team.clone(), game.solved.len());
+        self.apply_game_end(result);
+        self.log_operator(format!(
+            "Game over: {} {} after {}, {} puzzles solved, {} hints",
+            team,
+            result.label().to_lowercase(),
+            format_game_time(self.clock.elapsed()),
+            solved,
+            self.hints_given.len()
+        ));
+    }
+
+    // Stops the clock and adds the game to the history
+    fn apply_game_end(&mut self, result: GameResult) {
+        let Some(game) = self.game.take() else { return };
+        self.record(recording::Event::GameEnd { result });
+        self.clock.pause();
+        let puzzles = self.puzzles.keys().cloned();
+        let record = game.finish(result, timebase::local_now(), self.clock.elapsed(), &self.hints_given, puzzles);
+        if let Err(e) = self.history.append(record) {
+            self.log_marked(LogSource::Supervisor, LogLevel::Error, format!("{:#}", e));
+        }
+    }
+
+    fn open_new_game(&mut self) {
+        if self.game.is_some() {
+            let message = "A game is already running, end it with G first".to_string();
+            self.log_marked(LogSource::Operator, LogLevel::Warning, message);
         } else {
-            raw_line
+            self.open_prompt(PromptKind::NewGame);
+        }
+    }
+
+    // --- Tabs ---
+
+    // The new tab is saved right away, a failing state file is reported once
+    fn switch_tab(&mut self, tab: Tab) {
+        if tab == self.tab {
+            return;
+        }
+        self.tab = tab;
+        let Some(path) = &self.state_file else { return };
+        if let Err(e) = (UiState { tab }).save(path) {
+            self.state_file = None;
+            self.log_marked(LogSource::Supervisor, LogLevel::Error, format!("{:#}", e));
+        }
+    }
+
+    // Keys of the active screen, returns false for keys it does not use
+    fn handle_tab_key(&mut self, key: KeyEvent) -> bool {
+        match self.tab {
+            Tab::Overview => self.handle_log_key(key) || self.handle_client_key(key.code),
+            Tab::Puzzles => self.handle_puzzle_key(key.code),
+            Tab::System => false,
+            Tab::Clients => self.handle_client_key(key.code),
+            Tab::Logs => self.handle_log_key(key),
+            Tab::Game => self.handle_game_key(key.code),
+        }
+    }
+
+    fn handle_log_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            // Esc first clears an active search
+            KeyCode::Esc if self.search.is_some() => self.clear_search(),
+            KeyCode::Up => self.scroll_up(),
+            KeyCode::Down => self.scroll_down(),
+            KeyCode::PageUp => self.scroll_page_up(),
+            KeyCode::PageDown => self.scroll_page_down(),
+            KeyCode::Home => self.scroll_to_top(),
+            KeyCode::End => self.scroll_to_bottom(),
+            KeyCode::Char('e') => self.expand_tracebacks = !self.expand_tracebacks,
+            KeyCode::Char('/') => self.open_prompt(PromptKind::Search),
+            KeyCode::Char('n') => self.jump_to_match(true),
+            KeyCode::Char('N') => self.jump_to_match(false),
+            KeyCode::Char('f') => self.open_prompt(PromptKind::Filter),
+            KeyCode::Char('F') => self.set_filter(LogFilter::default()),
+            _ => return false,
+        }
+        true
+    }
+
+    // ↑/↓ select a puzzle, Enter opens its details, PgUp/PgDn scroll their log lines
+    fn handle_puzzle_key(&mut self, code: KeyCode) -> bool {
+        let names = self.puzzle_names();
+        let current = self.puzzle_selected.as_ref().and_then(|s| names.iter().position(|n| n == s));
+        let selected = match (code, current) {
+            (KeyCode::Up, Some(i)) => i.saturating_sub(1),
+            (KeyCode::Down, Some(i)) => (i + 1).min(names.len().saturating_sub(1)),
+            (KeyCode::Up | KeyCode::Down | KeyCode::Enter, None) => 0,
+            (KeyCode::Enter, Some(i)) => i,
+            (KeyCode::Esc, _) if self.puzzle_detail.is_some() => {
+                self.puzzle_detail = None;
+                return true;
+            }
+            (KeyCode::PageUp | KeyCode::PageDown | KeyCode::Home | KeyCode::End, _) => {
+                return self.scroll_puzzle_detail(code);
+            }
+            _ => return false,
         };
-        
-        self.logs.push(display_line);
+        let Some(name) = names.get(selected).cloned() else { return true };
 
-        // 3. Auto-Scroll
-        if self.logs.len() > 10 {
-            self.scroll_position = self.logs.len() - 10;
+        // The open pane follows the selection
+        if code == KeyCode::Enter || self.puzzle_detail.is_some() {
+            self.open_puzzle_detail(name);
         } else {
-            self.scroll_position = 0;
+            self.puzzle_selected = Some(name);
         }
+        true
     }
 
-    fn scroll_up(&mut self) {
-        if self.scroll_position > 0 {
-            self.scroll_position -= 1;
+    // Selects the puzzle and shows its details, an open pane for it stays as it is
+    fn open_puzzle_detail(&mut self, name: String) {
+        if self.puzzle_detail.as_ref().is_none_or(|d| d.name != name) {
+            self.puzzle_detail = Some(PuzzleDetail::new(name.clone()));
+            self.update_log_view();
         }
+        self.puzzle_selected = Some(name);
     }
 
-    fn scroll_down(&mut self) {
-        let max_scroll = self.logs.len().saturating_sub(10);
-        if self.scroll_position < max_scroll {
-            self.scroll_position += 1;
+    fn scroll_puzzle_detail(&mut self, code: KeyCode) -> bool {
+        let Some(detail) = &mut self.puzzle_detail else { return false };
+        let last = detail.log_view.len().saturating_sub(detail.log_height);
+        detail.log_offset = match code {
+            KeyCode::PageUp => (detail.log_offset + detail.log_height).min(last),
+            KeyCode::PageDown => detail.log_offset.saturating_sub(detail.log_height),
+            KeyCode::Home => last,
+            _ => 0,
+        };
+        true
+    }
+
+    fn puzzle_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.puzzles.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    fn handle_client_key(&mut self, code: KeyCode) -> bool {
+        if code != KeyCode::Char('o') {
+            return false;
+        }
+        self.client_sort = self.client_sort.next();
+        true
+    }
+
+    // ↑/↓ select a game in the history
+    fn handle_game_key(&mut self, code: KeyCode) -> bool {
+        let last = self.history.games.len().saturating_sub(1);
+        self.history_selected = match code {
+            KeyCode::Up => self.history_selected.saturating_sub(1),
+            KeyCode::Down => (self.history_selected + 1).min(last),
+            KeyCode::Home => 0,
+            KeyCode::End => last,
+            _ => return false,
+        };
+        true
+    }
+
+    // --- Hints ---
+
+    fn hint_puzzle(&self, index: usize) -> Option<&String> {
+        self.hints.puzzles().nth(index)
+    }
+
+    // ←/→ pick the puzzle, ↑/↓ the hint, Enter sends it
+    fn handle_hint_panel_key(&mut self, code: KeyCode) {
+        let Some(panel) = &self.hint_panel else { return };
+        let (mut puzzle, mut hint) = (panel.puzzle, panel.hint);
+        let puzzles = self.hints.puzzles().count();
+        let hints = self.hint_puzzle(puzzle).map_or(0, |p| self.hints.hints(p).len());
+        match code {
+            KeyCode::Esc | KeyCode::Char('h') => {
+                self.hint_panel = None;
+                return;
+            }
+            KeyCode::Left if puzzle > 0 => (puzzle, hint) = (puzzle - 1, 0),
+            KeyCode::Right if puzzle + 1 < puzzles => (puzzle, hint) = (puzzle + 1, 0),
+            KeyCode::Up => hint = hint.saturating_sub(1),
+            KeyCode::Down if hint + 1 < hints => hint += 1,
+            KeyCode::Enter => self.send_hint(puzzle, hint),
+            _ => {}
         }
+        self.hint_panel = Some(HintPanel { puzzle, hint });
+    }
+
+    // A replay only shows the hints given in the recording
+    fn send_hint(&mut self, puzzle: usize, hint: usize) {
+        if self.replay_status.is_some() {
+            return;
+        }
+        let Some(name) = self.hint_puzzle(puzzle).cloned() else { return };
+        let Some(text) = self.hints.hints(&name).get(hint).cloned() else { return };
+        self.pending_hint = Some((name, text));
+    }
+
+    // Called by `hints::deliver` once the server has the hint, a failed one is not counted
+    fn hint_delivered(&mut self, puzzle: String, text: String) {
+        self.apply_hint(puzzle.clone(), text.clone());
+        let given = self.hints_given.last().map_or(String::new(), |h| h.time_label());
+        self.log_operator(format!("Hint {} for {} {}: {}", self.hints_given.len(), puzzle, given, text));
+    }
+
+    // Counts the hint and places it on the game timeline
+    fn apply_hint(&mut self, puzzle: String, text: String) {
+        self.record(recording::Event::Hint { puzzle: puzzle.clone(), text: text.clone() });
+        let game_time = (!self.clock.is_idle()).then(|| self.clock.elapsed());
+        self.hints_given.push(GivenHint { puzzle, text, at: timebase::local_now(), game_time });
+    }
+
+    fn update_log_view(&mut self) {
+        let changed = self.logs_changed.take();
+        self.log_view.update(&self.logs, changed, &self.filter, self.search.as_ref(), &self.puzzles);
+        if let Some(detail) = &mut self.puzzle_detail {
+            detail.log_view.update(&self.logs, changed, &detail.filter, None, &self.puzzles);
+        }
+    }
+
+    // Height of the entry at a position in the view
+    fn entry_height(&self, position: usize) -> usize {
+        self.logs.get(self.log_view.index(position)).map_or(0, |e| e.height(self.expand_tracebacks))
+    }
+
+    // First entry that shows the newest lines when the pane is filled from the bottom
+    fn tail_start(&self) -> usize {
+        let mut rows = 0;
+        for i in (0..self.log_view.len()).rev() {
+            rows += self.entry_height(i);
+            if rows > self.log_view_height {
+                return i + 1;
+            }
+        }
+        0
+    }
+
+    fn log_start(&self) -> usize {
+        if self.follow_logs { self.tail_start() } else { self.scroll_position }
+    }
+
+    fn scroll_to(&mut self, position: usize) {
+        let tail = self.tail_start();
+        self.follow_logs = position >= tail;
+        self.scroll_position = position.min(tail);
+    }
+
+    fn scroll_up(&mut self) {
+        self.scroll_to(self.log_view_start.saturating_sub(1));
+    }
+
+    fn scroll_down(&mut self) {
+        self.scroll_to(self.log_view_start + 1);
     }
 
     fn scroll_page_up(&mut self) {
-        self.scroll_position = self.scroll_position.saturating_sub(10);
+        self.scroll_to(self.log_view_start.saturating_sub(self.log_view_height));
     }
 
     fn scroll_page_down(&mut self) {
-        let max_scroll = self.logs.len().saturating_sub(10);
-        self.scroll_position = (self.scroll_position + 10).min(max_scroll);
+        self.scroll_to(self.log_view_start + self.log_view_height);
     }
 
     fn scroll_to_top(&mut self) {
-        self.scroll_position = 0;
+        self.scroll_to(0);
     }
 
     fn scroll_to_bottom(&mut self) {
-        self.scroll_position = self.logs.len().saturating_sub(10);
+        self.follow_logs = true;
+    }
+
+    // --- Search & Filter ---
+
+    fn open_prompt(&mut self, kind: PromptKind) {
+        let input = match kind {
+            PromptKind::Search | PromptKind::Command | PromptKind::NewGame => String::new(),
+            PromptKind::Filter => self.filter.text.clone(),
+        };
+        self.prompt = Some(Prompt { kind, input });
+        self.prompt_error = None;
+        self.history_position = None;
+    }
+
+    fn handle_prompt_key(&mut self, key: KeyEvent) {
+        if self.prompt.as_ref().is_some_and(|p| p.kind == PromptKind::Command) {
+            match key.code {
+                KeyCode::Up => return self.browse_history(true),
+                KeyCode::Down => return self.browse_history(false),
+                KeyCode::Tab => return self.complete_command(),
+                _ => {}
+            }
+        }
+
+        let Some(prompt) = &mut self.prompt else { return };
+        match key.code {
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => prompt.input.push(c),
+            KeyCode::Backspace => {
+                prompt.input.pop();
+            }
+            KeyCode::Enter => return self.submit_prompt(),
+            KeyCode::Esc => return self.cancel_prompt(),
+            _ => return,
+        }
+        self.prompt_error = None;
+
+        // Matches are highlighted while typing
+        if prompt.kind == PromptKind::Search {
+            let search = search_regex(&prompt.input).ok().filter(|_| !prompt.input.is_empty());
+            self.set_search(search);
+        }
+    }
+
+    fn submit_prompt(&mut self) {
+        let Some(prompt) = self.prompt.take() else { return };
+        let result = match prompt.kind {
+            PromptKind::Search if prompt.input.is_empty() => {
+                self.clear_search();
+                return;
+            }
+            PromptKind::Search => search_regex(&prompt.input).map(|regex| {
+                self.set_search(Some(regex));
+                self.search_text = prompt.input.clone();
+                self.search_match = None;
+                self.jump_to_match(false);
+            }),
+            PromptKind::Filter => LogFilter::parse(&prompt.input).map(|filter| self.set_filter(filter)),
+            PromptKind::Command => {
+                self.submit_command(prompt.input.trim().to_string());
+                Ok(())
+            }
+            PromptKind::NewGame => parse_team(&prompt.input).map(|(team, players)| self.start_game(team, players)),
+        };
+
+        // Keep the prompt open so the input can be fixed
+        if let Err(e) = result {
+            self.prompt_error = Some(e.to_string());
+            self.prompt = Some(prompt);
+        }
+    }
+
+    fn cancel_prompt(&mut self) {
+        if let Some(Prompt { kind: PromptKind::Search, .. }) = self.prompt.take() {
+            let search = search_regex(&self.search_text).ok().filter(|_| !self.search_text.is_empty());
+            self.set_search(search);
+        }
+        self.prompt_error = None;
+    }
+
+    // --- Command Bar ---
+
+    fn submit_command(&mut self, command: String) {
+        if command.is_empty() {
+            return;
+        }
+        if self.command_history.last() != Some(&command) {
+            self.command_history.push(command.clone());
+        }
+        self.log_operator(format!("> {}", command));
+        self.pending_command = Some(command);
+    }
+
+    fn browse_history(&mut self, older: bool) {
+        let Some(prompt) = &mut self.prompt else { return };
+        let len = self.command_history.len();
+        self.history_position = match (self.history_position, older) {
+            (None, true) => len.checked_sub(1),
+            (Some(pos), true) => Some(pos.saturating_sub(1)),
+            (Some(pos), false) if pos + 1 < len => Some(pos + 1),
+            (_, false) => None,
+        };
+        prompt.input = self.history_position.map_or_else(String::new, |pos| self.command_history[pos].clone());
+    }
+
+    // Puzzle names starting with the last word of the input
+    fn completions(&self, input: &str) -> Vec<&str> {
+        let word = input.rsplit(' ').next().unwrap_or("");
+        let mut names: Vec<&str> = self.puzzles.keys()
+            .map(String::as_str)
+            .filter(|name| name.starts_with(word))
+            .collect();
+        names.sort();
+        names
+    }
+
+    // Tab completes a unique name, or as far as all candidates agree
+    fn complete_command(&mut self) {
+        let Some(prompt) = &self.prompt else { return };
+        let names = self.completions(&prompt.input);
+        let Some(first) = names.first() else { return };
+        let completion = if names.len() == 1 {
+            format!("{} ", first)
+        } else {
+            let mut prefix = first.to_string();
+            for name in &names[1..] {
+                let common = prefix.chars().zip(name.chars()).take_while(|(a, b)| a == b).count();
+                prefix = prefix.chars().take(common).collect();
+            }
+            prefix
+        };
+
+        if let Some(prompt) = &mut self.prompt {
+            let start = prompt.input.rfind(' ').map_or(0, |i| i + 1);
+            prompt.input.truncate(start);
+            prompt.input.push_str(&completion);
+        }
+    }
+
+    fn clear_search(&mut self) {
+        self.set_search(None);
+        self.search_text.clear();
+        self.search_match = None;
+    }
+
+    fn set_search(&mut self, search: Option<Regex>) {
+        self.search = search;
+        self.log_view.invalidate();
+        self.update_log_view();
+    }
+
+    fn set_filter(&mut self, filter: LogFilter) {
+        self.filter = filter;
+        self.follow_logs = true;
+        self.log_view.invalidate();
+        self.update_log_view();
+    }
+
+    // n jumps to the next newer match, N to the next older one, both wrap around.
+    // Without a current match the search starts from the bottom of the pane.
+    fn jump_to_match(&mut self, forward: bool) {
+        let matches = self.log_view.matches();
+        // Matches are log indices in the same order as the view
+        let view_end = if self.log_view_end < self.log_view.len() {
+            self.log_view.index(self.log_view_end)
+        } else {
+            usize::MAX
+        };
+        let target = match (self.search_match, forward) {
+            (Some(current), true) => matches.iter().find(|&&i| i > current).or(matches.first()),
+            (Some(current), false) => matches.iter().rev().find(|&&i| i < current).or(matches.last()),
+            (None, _) => matches.iter().rev().find(|&&i| i < view_end).or(matches.last()),
+        };
+        let Some(&index) = target else {
+            self.search_match = None;
+            return;
+        };
+
+        self.search_match = Some(index);
+        let Some(pos) = self.log_view.position(index) else { return };
+        if pos < self.log_view_start || pos >= self.log_view_end {
+            self.scroll_to(pos.saturating_sub(self.log_view_height / 2));
+        }
     }
 }
 
 fn main() -> Result<()> {
+    // Load config and start the server before touching the terminal, so any
+    // error is printed normally instead of inside raw mode
+    let config = Config::load()?;
+
+    let app = Arc::new(Mutex::new(App::new(&config)?));
+    let mut process_monitor = ProcessMonitor::new(&config.process);
+    let hints_config = config.hints.clone();
+
+    // A replay feeds the recording instead of running the server
+    let mut replay = config.replay.as_deref().map(Replay::load).transpose()?;
+    let mut supervisor = None;
+    if replay.is_none() {
+        let mut server = Supervisor::new(config.server, config.supervisor, app.clone());
+        server.start()?;
+        supervisor = Some(server);
+        probe::spawn(config.probe, app.clone());
+        if let Some(file) = config.rules.file.filter(|_| config.rules.reload) {
+            rules::watch(file, app.clone());
+        }
+    }
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let app = Arc::new(Mutex::new(App::new()));
-    let app_clone = app.clone();
-    let child_process: Arc<Mutex<Option<Child>>> = Arc::new(Mutex::new(None));
-    let child_process_clone = child_process.clone();
-
-    thread::spawn(move || {
-        // IMPORTANT: "-u" forces unbuffered output so we see logs immediately
-        let mut child = Command::new("python3")
-            .arg("-u") 
-            .arg("server.py")
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .expect("Failed to start python script");
-
-        let stdout = child.stdout.take().expect("Failed to capture stdout");
-        let stderr = child.stderr.take().expect("Failed to capture stderr");
-
-        *child_process_clone.lock().unwrap() = Some(child);
-
-        let reader = BufReader::new(stdout);
-        let stderr_reader = BufReader::new(stderr);
-
-        // Spawn Stderr Thread
-        let app_stderr = app_clone.clone();
-        thread::spawn(move || {
-            for line in stderr_reader.lines() {
-                if let Ok(l) = line {
-                    let mut app = app_stderr.lock().unwrap();
-                    app.process_log(l, true); // Process as stderr
-                }
-            }
-        });
-
-        // Main Stdout Loop
-        for line in reader.lines() {
-            if let Ok(l) = line {
-                let mut app = app_clone.lock().unwrap();
-                app.process_log(l, false); // Process as stdout
-            }
-        }
-    });
-
     // UI Loop
     let mut sys = System::new_with_specifics(
         RefreshKind::nothing().with_cpu(CpuRefreshKind::everything()).with_memory(MemoryRefreshKind::everything()),
     );
 
     loop {
+        if let Some(supervisor) = &mut supervisor {
+            supervisor.tick();
+        }
+        if let Some(replay) = &mut replay {
+            replay.tick(&mut app.lock().unwrap());
+        }
+
         sys.refresh_cpu_all();
         sys.refresh_memory();
+        let server_process = process_monitor.refresh(supervisor.as_ref().and_then(Supervisor::pid));
         
         {
             let mut app = app.lock().unwrap();
@@ -233,64 +1121,223 @@ fn main() -> Result<()> {
             app.ram_usage = sys.used_memory() / 1024 / 1024;
             app.total_ram = sys.total_memory() / 1024 / 1024;
             app.uptime = System::uptime();
+            app.server_process = server_process;
+            if replay.is_none() {
+                app.sample_metrics();
+            }
+            app.check_puzzles();
             
             if app.should_quit {
                 break;
             }
         }
 
-        terminal.draw(|f| ui(f, &app.lock().unwrap()))?;
+        terminal.draw(|f| ui(f, &mut app.lock().unwrap()))?;
 
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            // Actions that need the supervisor are collected here and run after
+            // the app lock is released, since the supervisor locks the app itself
+            let mut server_action = None;
+            let command;
+            let hint;
+            {
                 let mut app = app.lock().unwrap();
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => app.should_quit = true,
-                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        app.should_quit = true
+                // Clicks on buttons arrive as the key they stand for
+                let key = match event {
+                    Event::Key(key) => Some(key),
+                    Event::Mouse(mouse) => app.handle_mouse(mouse),
+                    _ => None,
+                };
+                if let Some(key) = key {
+                    if let Some(confirm) = app.pending_confirm.take() {
+                        match (confirm, key.code) {
+                            (Confirm::EndGame, KeyCode::Char('e')) => app.end_game(GameResult::Escaped),
+                            (Confirm::EndGame, KeyCode::Char('f')) => app.end_game(GameResult::Failed),
+                            (Confirm::Server(action), KeyCode::Char('y') | KeyCode::Enter) => server_action = Some(action),
+                            (Confirm::ResetClock, KeyCode::Char('y') | KeyCode::Enter) => app.reset_clock(),
+                            _ => {}
+                        }
+                    } else if app.prompt.is_some() {
+                        app.handle_prompt_key(key);
+                    } else if app.hint_panel.is_some() {
+                        app.handle_hint_panel_key(key.code);
+                    } else if app.stats.is_some() {
+                        if matches!(key.code, KeyCode::Esc | KeyCode::Char('L')) {
+                            app.stats = None;
+                        }
+                    } else if let Some(replay) = &mut replay
+                        && replay.handle_key(key.code, &mut app)
+                    {
+                        // Handled by the replay
+                    } else if app.handle_tab_key(key) {
+                        // Handled by the active screen
+                    } else {
+                        match key.code {
+                            KeyCode::Char('q') | KeyCode::Esc => app.should_quit = true,
+                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                app.should_quit = true
+                            }
+                            KeyCode::Char(c) if c.is_ascii_digit() => {
+                                if let Some(tab) = Tab::from_key(c) {
+                                    app.switch_tab(tab);
+                                }
+                            }
+                            KeyCode::Tab => {
+                                let tab = app.tab.next();
+                                app.switch_tab(tab);
+                            }
+                            KeyCode::BackTab => {
+                                let tab = app.tab.previous();
+                                app.switch_tab(tab);
+                            }
+                            KeyCode::Char('S') => app.pending_confirm = Some(Confirm::Server(ServerAction::Start)),
+                            KeyCode::Char('X') => app.pending_confirm = Some(Confirm::Server(ServerAction::Stop)),
+                            KeyCode::Char('R') => app.pending_confirm = Some(Confirm::Server(ServerAction::Restart)),
+                            KeyCode::Char('t') => app.toggle_clock(),
+                            KeyCode::Char('T') => app.confirm_reset_clock(),
+                            KeyCode::Char('p') => app.switch_tab(Tab::Puzzles),
+                            KeyCode::Char('m') => app.switch_tab(Tab::System),
+                            KeyCode::Char('H') => app.switch_tab(Tab::Game),
+                            KeyCode::Char('a') => app.alert = None,
+                            KeyCode::Char(':') => app.open_prompt(PromptKind::Command),
+                            KeyCode::Char('h') => app.hint_panel = Some(HintPanel::default()),
+                            KeyCode::Char('g') => app.open_new_game(),
+                            KeyCode::Char('G') if app.game.is_some() => app.pending_confirm = Some(Confirm::EndGame),
+                            KeyCode::Char('L') => app.stats = Some(Stats::compute(&app.history.games)),
+                            _ => {}
+                        }
                     }
-                    KeyCode::Up => app.scroll_up(),
-                    KeyCode::Down => app.scroll_down(),
-                    KeyCode::PageUp => app.scroll_page_up(),
-                    KeyCode::PageDown => app.scroll_page_down(),
-                    KeyCode::Home => app.scroll_to_top(),
-                    KeyCode::End => app.scroll_to_bottom(),
-                    _ => {}
                 }
+                command = app.pending_command.take();
+                hint = app.pending_hint.take();
+            }
+            if let Some(action) = server_action
+                && let Some(supervisor) = &mut supervisor
+            {
+                supervisor.run(action);
+            }
+            if let Some(command) = command
+                && let Some(supervisor) = &mut supervisor
+            {
+                supervisor.send(&command);
+            }
+            if let Some((puzzle, text)) = hint {
+                hints::deliver(&hints_config, &puzzle, &text, supervisor.as_mut(), &app);
             }
         }
     }
 
-    // Cleanup
-    {
-        let mut child_opt = child_process.lock().unwrap();
-        if let Some(mut child) = child_opt.take() {
-            let _ = child.kill();
-            let _ = child.wait();
-        }
-    }
-
+    // Cleanup (restore the terminal first, stopping the server may take the grace period)
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
     terminal.show_cursor()?;
 
+    if let Some(mut supervisor) = supervisor {
+        println!("Stopping server...");
+        supervisor.shutdown();
+    }
+
     Ok(())
 }
 
-fn ui(f: &mut Frame, app: &App) {
+fn ui(f: &mut Frame, app: &mut App) {
+    app.regions = Regions::default();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
-            Constraint::Length(3),  // Header
-            Constraint::Min(10),    // Main
-            Constraint::Length(12), // Logs
+            Constraint::Length(1),  // Tab bar
+            Constraint::Length(6),  // Header
+            Constraint::Min(10),    // Active screen
+            Constraint::Length(1),  // Search & filter bar
         ])
         .split(f.area());
 
-    // Header
-    let status_color = if app.server_ready { Color::Green } else { Color::Yellow };
-    let status_text = if app.server_ready { "ONLINE" } else { "STARTING" };
+    render_tab_bar(f, app, chunks[0]);
+    render_header(f, app, chunks[1]);
+    match app.tab {
+        Tab::Overview => render_overview(f, app, chunks[2]),
+        Tab::Puzzles => render_puzzles_tab(f, app, chunks[2]),
+        Tab::Clients => render_client_list(f, app, chunks[2]),
+        Tab::Logs => render_logs(f, app, chunks[2]),
+        Tab::System => render_system(f, app, chunks[2]),
+        Tab::Game => render_game_tab(f, app, chunks[2]),
+    }
+    render_log_bar(f, app, chunks[3]);
+
+    if let Some(panel) = &app.hint_panel {
+        render_hint_panel(f, app, panel);
+    }
+    if let Some(stats) = &app.stats {
+        render_stats(f, stats);
+    }
+
+    // Confirmation Prompt
+    if let Some(confirm) = app.pending_confirm {
+        const GAP: &str = "   ";
+        let buttons = confirm.buttons();
+        let keys = buttons.iter().map(|(label, _)| *label).collect::<Vec<_>>().join(GAP);
+        let keys_width = keys.chars().count() as u16;
+        let width = (keys_width + 4).max(40);
+        let area = centered_rect(width, 5, f.area());
+        let prompt = Paragraph::new(vec![
+            Line::from(confirm.prompt()),
+            Line::from(""),
+            Line::from(keys).style(Style::default().fg(Color::DarkGray)),
+        ])
+        .block(Block::default().borders(Borders::ALL).title(" Confirm "))
+        .style(Style::default().fg(Color::White).bg(Color::Black))
+        .alignment(Alignment::Center);
+        f.render_widget(Clear, area);
+        f.render_widget(prompt, area);
+
+        // Each label is a button, at the same place the centered line put it
+        let inner = area.inner(Margin { vertical: 1, horizontal: 1 });
+        let mut x = inner.x + inner.width.saturating_sub(keys_width) / 2;
+        for (label, code) in buttons {
+            let label_width = label.chars().count() as u16;
+            app.regions.buttons.push((Rect::new(x, inner.y + 2, label_width, 1), *code));
+            x += label_width + GAP.len() as u16;
+        }
+        app.regions.popup = Some(area);
+    }
+}
+
+fn render_tab_bar(f: &mut Frame, app: &mut App, area: Rect) {
+    let titles: Vec<String> = Tab::ALL.iter().enumerate().map(|(i, tab)| format!(" {} {} ", i + 1, tab.label())).collect();
+
+    // Titles are laid out left to right, one space apart
+    let mut x = area.x;
+    for (title, tab) in titles.iter().zip(Tab::ALL) {
+        let width = title.chars().count() as u16;
+        app.regions.tabs.push((Rect::new(x, area.y, width, 1).intersection(area), tab));
+        x += width + 1;
+    }
+
+    let tabs = Tabs::new(titles)
+        .select(app.tab.index())
+        .style(Style::default().fg(Color::DarkGray))
+        .highlight_style(Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .padding("", "")
+        .divider(" ");
+    f.render_widget(tabs, area);
+}
+
+// Server status, host stats and the game clock, shown on every tab
+fn render_header(f: &mut Frame, app: &mut App, area: Rect) {
+    let header_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(30)])
+        .split(area);
+
+    let status_color = match app.server_status {
+        ServerStatus::Online => Color::Green,
+        ServerStatus::Starting | ServerStatus::Stopping => Color::Yellow,
+        ServerStatus::Stopped(_) => Color::Gray,
+        ServerStatus::Crashed(_) | ServerStatus::Restarting { .. } => Color::Red,
+    };
+    let status_text = app.server_status.label();
 
     let uptime_str = format!("{}s", app.uptime);
     let info_text = format!(
@@ -298,60 +1345,1039 @@ fn ui(f: &mut Frame, app: &App) {
         app.hostname, app.ip_address, uptime_str, app.cpu_usage, app.ram_usage, app.total_ram, status_text
     );
     
-    let header = Paragraph::new(info_text)
-        .block(Block::default().borders(Borders::ALL).title(" System Monitor "))
+    // Custom fields from `set_field` rules above, the latest alert below the stats
+    let fields_text = app.fields.iter()
+        .map(|(k, v)| format!("{}: {}", k, v))
+        .collect::<Vec<_>>()
+        .join(" | ");
+    let alert_line = match &app.alert {
+        Some(alert) => Line::from(format!(" ⚠ ALERT {} (a to dismiss) ", alert))
+            .style(Style::default().fg(Color::White).bg(Color::Red)),
+        None => Line::from(""),
+    };
+
+    let log_file_title = match (&app.replay_status, &app.session_log) {
+        (Some(replay), _) => Line::from(format!(" Replay: {} ", replay)),
+        (None, Some(log)) => Line::from(format!(" Log file: {} ", log.path().display())),
+        (None, None) => Line::from(" Log file: off "),
+    };
+    let key_hints = if app.replay_status.is_some() {
+        " Space pause | ←/→ seek 10s | [/] seek 1min | +/- speed | 1-6/Tab switch tab | q quit "
+    } else {
+        " S start | X stop | R restart | t clock | T reset clock | 1-6/Tab switch tab | q quit "
+    };
+
+    let header = Paragraph::new(vec![Line::from(fields_text), Line::from(info_text), process_line(app), alert_line])
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" System Monitor ")
+                .title(log_file_title.right_aligned())
+                .title_bottom(Line::from(key_hints).right_aligned()),
+        )
         .style(Style::default().fg(Color::Black).bg(status_color).add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center);
-    f.render_widget(header, chunks[0]);
+    f.render_widget(header, header_chunks[0]);
+    if app.alert.is_some() {
+        // Fourth line inside the border
+        let row = header_chunks[0].inner(Margin { vertical: 1, horizontal: 1 });
+        app.regions.alert = Some(Rect::new(row.x, row.y + 3, row.width, 1).intersection(row));
+    }
 
-    // Main Content
-    let main_chunks = Layout::default()
+    // Game Clock
+    let clock_color = app.clock.color();
+    let clock = Paragraph::new(
+        clock::big_text(&app.clock.display())
+            .into_iter()
+            .map(Line::from)
+            .collect::<Vec<_>>(),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(match &app.game {
+                Some(game) => format!(" {} ({}) · {} ", game.team, game.players, app.clock.state_label()),
+                None => format!(" Game: {} ", app.clock.state_label()),
+            })
+            .title_bottom(Line::from(format!(" Hints: {} (h) ", app.hints_given.len())).right_aligned()),
+    )
+    .style(Style::default().fg(clock_color).add_modifier(Modifier::BOLD))
+    .alignment(Alignment::Center);
+    f.render_widget(clock, header_chunks[1]);
+
+}
+
+// The old single screen: puzzles and clients side by side above the logs
+fn render_overview(f: &mut Frame, app: &mut App, area: Rect) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(10), Constraint::Length(12)])
+        .split(area);
+    let columns = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(chunks[1]);
+        .split(rows[0]);
+    render_puzzle_list(f, app, columns[0]);
+    render_client_list(f, app, columns[1]);
+    render_logs(f, app, rows[1]);
+}
 
-    // Puzzles
-    let puzzle_items: Vec<ListItem> = app.puzzles.values()
+// The puzzle list with the details of the selected puzzle, or the probe
+// latency of all puzzles, below
+fn render_puzzles_tab(f: &mut Frame, app: &mut App, area: Rect) {
+    let list_height = if app.puzzle_detail.is_some() { 35 } else { 50 };
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(list_height), Constraint::Min(0)])
+        .split(area);
+    render_puzzle_list(f, app, rows[0]);
+    if app.puzzle_detail.is_some() {
+        render_puzzle_detail(f, app, rows[1]);
+    } else {
+        render_probe_details(f, app, rows[1]);
+    }
+}
+
+fn render_puzzle_list(f: &mut Frame, app: &mut App, area: Rect) {
+    let mut puzzles: Vec<&Puzzle> = app.puzzles.values().collect();
+    puzzles.sort_by(|a, b| a.name.cmp(&b.name));
+    let solved = puzzles.iter().filter(|p| p.state == PuzzleState::Solved).count();
+
+    // Configured registration attributes (or `set_field` values) as `key=value`, padded to line up
+    let column = |p: &Puzzle, key: &str| {
+        let value = p.attribute(key).or_else(|| p.fields.get(key).cloned());
+        format!("{}={}", key, value.unwrap_or_else(|| "-".to_string()))
+    };
+    let widths: Vec<usize> = app
+        .puzzle_columns
+        .iter()
+        .map(|key| puzzles.iter().map(|p| column(p, key).chars().count()).max().unwrap_or(0))
+        .collect();
+
+    let puzzle_items: Vec<ListItem> = puzzles.iter()
         .map(|p| {
-            ListItem::new(format!("🧩 {} ({})", p.name, p.ip))
-                .style(Style::default().fg(Color::Green))
+            let liveness = p.liveness(app.puzzle_stale_after, app.puzzle_offline_after);
+            let mut spans = vec![Span::raw(format!("🧩 {:<14} {:<15} ", p.name, p.ip))];
+            for (key, width) in app.puzzle_columns.iter().zip(&widths) {
+                spans.push(Span::styled(format!("{:<width$} ", column(p, key)), Style::default().fg(Color::Cyan)));
+            }
+            spans.extend([
+                Span::styled(format!("{:<8} ", format_age(p.age())), Style::default().fg(liveness.color())),
+                Span::styled(p.state.label(), Style::default().fg(p.state.color()).add_modifier(Modifier::BOLD)),
+            ]);
+            if liveness != Liveness::Online {
+                spans.push(Span::styled(
+                    format!(" {}", liveness.label()),
+                    Style::default().fg(Color::Black).bg(liveness.color()).add_modifier(Modifier::BOLD),
+                ));
+            }
+            if let Some(solved_label) = p.solved_label() {
+                spans.push(Span::styled(format!(" {}", solved_label), Style::default().fg(Color::DarkGray)));
+            }
+            for (key, value) in &p.fields {
+                spans.push(Span::styled(format!(" {}={}", key, value), Style::default().fg(Color::DarkGray)));
+            }
+            if p.probe.has_data() {
+                let color = if p.probe.last_rtt.is_some() { Color::Green } else { Color::Red };
+                spans.push(Span::styled(format!(" [{}]", p.probe.summary()), Style::default().fg(color)));
+            }
+            ListItem::new(Line::from(spans))
         })
         .collect();
     
+    // Selection only exists on the puzzles tab, elsewhere the arrows scroll the logs
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Active Puzzles ({}/{} solved) ", solved, puzzles.len()));
+    let mut state = ListState::default();
+    if app.tab == Tab::Puzzles {
+        block = block.title_bottom(Line::from(" ↑/↓ select | Enter details ").right_aligned());
+        state.select(app.puzzle_selected.as_ref().and_then(|s| puzzles.iter().position(|p| p.name == *s)));
+    }
     let puzzle_list = List::new(puzzle_items)
-        .block(Block::default().borders(Borders::ALL).title(format!(" Active Puzzles ({}) ", app.puzzles.len())));
-    f.render_widget(puzzle_list, main_chunks[0]);
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+    let items = puzzles.iter().map(|p| p.name.clone()).collect();
+    f.render_stateful_widget(puzzle_list, area, &mut state);
+    app.regions.puzzles = Some(ListRegion {
+        area: area.inner(Margin { vertical: 1, horizontal: 1 }),
+        offset: state.offset(),
+        items,
+    });
+}
+
+// Everything known about one puzzle: facts and state history on the left,
+// the registration dict and the log lines mentioning it on the right
+fn render_puzzle_detail(f: &mut Frame, app: &mut App, area: Rect) {
+    app.update_log_view();
+    let Some(detail) = &app.puzzle_detail else { return };
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Puzzle {} ", detail.name))
+        .title_bottom(Line::from(" ↑/↓ puzzle | PgUp/PgDn scroll lines | Esc close ").right_aligned());
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let Some(puzzle) = app.puzzles.get(&detail.name) else {
+        let text = format!("{} has not shown up in the logs (yet).", detail.name);
+        f.render_widget(Paragraph::new(text).alignment(Alignment::Center), inner);
+        return;
+    };
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(48), Constraint::Min(0)])
+        .split(inner);
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(7), Constraint::Length(3), Constraint::Min(0)])
+        .split(columns[0]);
+    // Room for the attributes, the raw dict and the top border
+    let registration_height = (puzzle.attributes.len() as u16 + 4).clamp(5, 14);
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(registration_height), Constraint::Min(0)])
+        .split(columns[1]);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let time = |at: DateTime<Local>| at.format("%H:%M:%S").to_string();
+    let liveness = puzzle.liveness(app.puzzle_stale_after, app.puzzle_offline_after);
+    let mut state = vec![
+        Span::raw(" State:      "),
+        Span::styled(puzzle.state.label(), Style::default().fg(puzzle.state.color()).add_modifier(Modifier::BOLD)),
+    ];
+    if let Some(solved_label) = puzzle.solved_label() {
+        state.push(Span::styled(format!(" {}", solved_label), dim));
+    }
+    let facts = vec![
+        Line::from(format!(" Name:       {}", puzzle.name)),
+        Line::from(format!(" IP:         {}", puzzle.ip)),
+        Line::from(state),
+        Line::from(format!(" Registered: {}", puzzle.registered_at.map_or("-".to_string(), time))),
+        Line::from(vec![
+            Span::raw(format!(" Last seen:  {} ", time(puzzle.last_seen))),
+            Span::styled(format!("{} {}", format_age(puzzle.age()), liveness.label()), Style::default().fg(liveness.color())),
+        ]),
+        Line::from(format!(" Fields:     {}", puzzle.fields.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join(" "))),
+    ];
+    f.render_widget(Paragraph::new(facts), left[0]);
 
-    // Clients
-    let client_items: Vec<ListItem> = app.clients.iter()
-        .map(|ip| ListItem::new(format!("💻 Client: {}", ip)).style(Style::default().fg(Color::Blue)))
+    let probe = &puzzle.probe;
+    let (probe_title, color) = if probe.has_data() {
+        let color = if probe.last_rtt.is_some() { Color::Green } else { Color::Red };
+        (format!(" Probe: {} ", probe.summary()), color)
+    } else {
+        (" Probe: no results ".to_string(), Color::DarkGray)
+    };
+    let data = probe.sparkline();
+    let data = &data[data.len().saturating_sub(left[1].width as usize)..];
+    let sparkline = Sparkline::default()
+        .block(Block::default().borders(Borders::TOP).title(probe_title))
+        .data(data)
+        .style(Style::default().fg(color));
+    f.render_widget(sparkline, left[1]);
+
+    // Newest state change first
+    let history: Vec<ListItem> = puzzle
+        .history
+        .iter()
+        .rev()
+        .map(|change| {
+            let game_time = change.game_time.map_or(String::new(), |t| format!("@ {}", format_game_time(t)));
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {} {:<8} ", time(change.at), game_time), dim),
+                Span::styled(change.state.label(), Style::default().fg(change.state.color())),
+            ]))
+        })
         .collect();
+    let title = format!(" State history ({}) ", puzzle.history.len());
+    f.render_widget(List::new(history).block(Block::default().borders(Borders::TOP).title(title)), left[2]);
 
-    let client_list = List::new(client_items)
-        .block(Block::default().borders(Borders::ALL).title(format!(" Connected Clients ({}) ", app.clients.len())));
-    f.render_widget(client_list, main_chunks[1]);
-
-    // Logs
-    let log_window_height = chunks[2].height as usize - 2;
-    let logs_to_show: Vec<ListItem> = app.logs.iter()
-        .skip(app.scroll_position)
-        .take(log_window_height)
-        .map(|s| ListItem::new(s.as_str()).style(Style::default().fg(Color::Gray)))
+    // Parsed attributes first, the dict as printed below them
+    let key_width = puzzle.attributes.keys().map(|k| k.chars().count()).max().unwrap_or(0);
+    let mut registration: Vec<Line> = puzzle
+        .attributes
+        .iter()
+        .map(|(key, value)| {
+            Line::from(vec![
+                Span::styled(format!(" {:<key_width$}  ", key), dim),
+                Span::raw(pyliteral::format(value)),
+            ])
+        })
+        .collect();
+    if !registration.is_empty() {
+        registration.push(Line::from(""));
+    }
+    registration.push(Line::styled(puzzle.registration.as_deref().unwrap_or("No registration line seen").to_string(), dim));
+    f.render_widget(
+        Paragraph::new(registration)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::LEFT | Borders::TOP).title(" Registration ")),
+        right[0],
+    );
+
+    // The newest lines fill the pane from the bottom unless scrolled back
+    let height = right[1].height.saturating_sub(1) as usize;
+    let total = detail.log_view.len();
+    let end = total.saturating_sub(detail.log_offset);
+    let items: Vec<ListItem> = (end.saturating_sub(height)..end)
+        .filter_map(|position| app.logs.get(detail.log_view.index(position)))
+        .flat_map(|entry| entry.render(false, None, false))
+        .map(ListItem::new)
         .collect();
+    let title = match detail.log_offset {
+        0 => format!(" Log lines ({}) ", total),
+        offset => format!(" Log lines ({}, {} newer below) ", total, offset),
+    };
+    f.render_widget(
+        List::new(items).block(Block::default().borders(Borders::LEFT | Borders::TOP).title(title)),
+        right[1],
+    );
+    app.regions.puzzle_detail_logs = Some(right[1]);
+    if let Some(detail) = &mut app.puzzle_detail {
+        detail.log_height = height;
+    }
+}
+
+// Active clients first, then a "recent" section for those that went quiet
+fn render_client_list(f: &mut Frame, app: &mut App, area: Rect) {
+    let mut clients: Vec<&Client> = app.clients.values().collect();
+    app.client_sort.sort(&mut clients);
+    let (active, recent): (Vec<&Client>, Vec<&Client>) =
+        clients.into_iter().partition(|c| c.is_active(app.client_timeout));
+
+    let client_line = |c: &Client, color: Color| {
+        let mut text = format!(
+            "💻 {:<15} http {:<4} udp {:<4} since {} {:<8}",
+            c.ip,
+            c.http_requests,
+            c.udp_messages,
+            c.first_seen.format("%H:%M"),
+            format_age(c.age())
+        );
+        if let Some(path) = &c.last_path {
+            text.push_str(&format!(" {}", path));
+        }
+        ListItem::new(text).style(Style::default().fg(color))
+    };
 
-    let logs_block = List::new(logs_to_show)
-        .block(Block::default().borders(Borders::ALL).title(" Server Logs "));
-    f.render_widget(logs_block, chunks[2]);
+    let mut client_items: Vec<ListItem> = active.iter().map(|c| client_line(c, Color::Blue)).collect();
+    if !recent.is_empty() {
+        client_items.push(
+            ListItem::new(format!("── recent ({}) ──", recent.len())).style(Style::default().fg(Color::DarkGray)),
+        );
+        client_items.extend(recent.iter().map(|c| client_line(c, Color::DarkGray)));
+    }
+
+    // Rows in the order of the items, the separator stands for no client
+    let mut rows: Vec<Option<String>> = active.iter().map(|c| Some(c.ip.clone())).collect();
+    if !recent.is_empty() {
+        rows.push(None);
+        rows.extend(recent.iter().map(|c| Some(c.ip.clone())));
+    }
+    let mut state = ListState::default();
+    state.select(app.client_selected.as_ref().and_then(|ip| rows.iter().position(|r| r.as_ref() == Some(ip))));
+
+    let client_list = List::new(client_items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Connected Clients ({}) · sort: {} ", active.len(), app.client_sort.label()))
+                .title_bottom(Line::from(" o sort ").right_aligned()),
+        )
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+    f.render_stateful_widget(client_list, area, &mut state);
+    app.regions.clients = Some(ListRegion {
+        area: area.inner(Margin { vertical: 1, horizontal: 1 }),
+        offset: state.offset(),
+        items: rows,
+    });
+}
+
+// Only the entries passing the filter, the scrollbar follows that view
+fn render_logs(f: &mut Frame, app: &mut App, area: Rect) {
+    let log_window_height = (area.height as usize).saturating_sub(2);
+    app.update_log_view();
+    app.log_view_height = log_window_height;
+    app.log_view_start = app.log_start();
+
+    // Only the shown entries are fetched, older ones may come from the spill file
+    let mut logs_to_show: Vec<ListItem> = Vec::new();
+    app.log_view_end = app.log_view_start;
+    while logs_to_show.len() < log_window_height && app.log_view_end < app.log_view.len() {
+        let index = app.log_view.index(app.log_view_end);
+        if let Some(entry) = app.logs.get(index) {
+            let current = app.search_match == Some(index);
+            logs_to_show.extend(
+                entry.render(app.expand_tracebacks, app.search.as_ref(), current).into_iter().map(ListItem::new),
+            );
+        }
+        app.log_view_end += 1;
+    }
+    logs_to_show.truncate(log_window_height);
+
+    let logs_title = if app.filter.is_active() {
+        format!(" Server Logs ({}/{} shown) ", app.log_view.len(), app.logs.len())
+    } else {
+        " Server Logs ".to_string()
+    };
+    let logs_block = List::new(logs_to_show).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(logs_title)
+            .title_bottom(Line::from(" ↑/↓ PgUp/PgDn scroll | e tracebacks | / search | f filter ").right_aligned()),
+    );
+    f.render_widget(logs_block, area);
     
     let scrollbar = Scrollbar::default()
         .orientation(ScrollbarOrientation::VerticalRight)
         .begin_symbol(Some("↑"))
         .end_symbol(Some("↓"));
-    let mut scroll_state = ScrollbarState::new(app.logs.len()).position(app.scroll_position);
+    let mut scroll_state = ScrollbarState::new(app.log_view.len()).position(app.log_view_start);
     
-    f.render_stateful_widget(
-        scrollbar,
-        chunks[2].inner(Margin { vertical: 1, horizontal: 0 }),
-        &mut scroll_state,
+    let scrollbar_area = area.inner(Margin { vertical: 1, horizontal: 0 });
+    f.render_stateful_widget(scrollbar, scrollbar_area, &mut scroll_state);
+    app.regions.logs = Some(area);
+    app.regions.log_scrollbar = Some(Rect { x: scrollbar_area.right().saturating_sub(1), width: 1, ..scrollbar_area });
+}
+
+// CPU, memory, threads and files of the server and its children, values over
+// their `[process]` threshold in red
+fn process_line(app: &App) -> Line<'static> {
+    if app.replay_status.is_some() {
+        return Line::from("");
+    }
+    let Some(p) = &app.server_process else {
+        return Line::from(" Server process: not running ");
+    };
+    let warnings = Warnings::check(&app.process_config, p);
+    let value = |text: String, warn: bool| {
+        if warn { Span::styled(text, Style::default().fg(Color::White).bg(Color::Red)) } else { Span::raw(text) }
+    };
+    let children = match p.children {
+        0 => String::new(),
+        1 => " + 1 child".to_string(),
+        n => format!(" + {} children", n),
+    };
+    Line::from(vec![
+        Span::raw(format!(" Server pid {}{} | CPU: ", p.pid, children)),
+        value(format!("{:.1}%", p.cpu), warnings.cpu),
+        Span::raw(" | RSS: "),
+        value(format!("{} MB", p.rss_mb()), warnings.rss),
+        Span::raw(" | Threads: "),
+        value(p.threads.to_string(), warnings.threads),
+        Span::raw(" | Files: "),
+        value(p.open_files.map_or("-".to_string(), |n| n.to_string()), warnings.open_files),
+        Span::raw(format!(" | Up: {} ", format_uptime(p.uptime))),
+    ])
+}
+
+// One line below the logs: the open prompt, or the active filter and search
+fn render_log_bar(f: &mut Frame, app: &App, area: Rect) {
+    let key = |text: &str| Span::styled(text.to_string(), Style::default().fg(Color::DarkGray));
+
+    let mut spans = Vec::new();
+    if let Some(prompt) = &app.prompt {
+        let label = match prompt.kind {
+            PromptKind::Search => " /",
+            PromptKind::Filter => " Filter: ",
+            PromptKind::Command => " :",
+            PromptKind::NewGame => " New game: ",
+        };
+        spans.push(Span::styled(label, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+        spans.push(Span::raw(format!("{}█", prompt.input)));
+        match &app.prompt_error {
+            Some(error) => spans.push(Span::styled(format!("  {}", error), Style::default().fg(Color::Red))),
+            None if prompt.kind == PromptKind::Filter => {
+                spans.push(key("  level:<min> source:<stream> puzzle:<name> ip:<addr> <regex> · Enter apply · Esc cancel"))
+            }
+            None if prompt.kind == PromptKind::Command => {
+                let names = app.completions(&prompt.input);
+                if !prompt.input.is_empty() && !names.is_empty() {
+                    spans.push(Span::styled(format!("  {}", names.join(" ")), Style::default().fg(Color::Cyan)));
+                }
+                spans.push(key("  Enter send · ↑/↓ history · Tab complete · Esc cancel"));
+            }
+            None if prompt.kind == PromptKind::NewGame => {
+                spans.push(key("  <team>, <players> · Enter start · Esc cancel"))
+            }
+            None => spans.push(key("  Enter jump · Esc cancel")),
+        }
+        f.render_widget(Paragraph::new(Line::from(spans)), area);
+        return;
+    }
+
+    if app.filter.is_active() {
+        spans.push(Span::styled(" Filter: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+        spans.push(Span::raw(app.filter.text.clone()));
+        spans.push(key("  (f edit · F clear)"));
+    } else {
+        spans.push(key(" No filter (f filter · / search · : command)"));
+    }
+
+    if app.search.is_some() {
+        let matches = app.log_view.matches();
+        let current = app.search_match.and_then(|m| matches.iter().position(|&i| i == m));
+        let count = match current {
+            Some(current) => format!("{}/{}", current + 1, matches.len()),
+            None => format!("{} found", matches.len()),
+        };
+        spans.push(Span::raw("   │ "));
+        spans.push(Span::styled(format!("/{}", app.search_text), Style::default().fg(Color::Yellow)));
+        spans.push(Span::raw(format!(" {}", count)));
+        spans.push(key("  (n/N jump · Esc clear)"));
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+// A latency sparkline per puzzle
+fn render_probe_details(f: &mut Frame, app: &App, area: Rect) {
+    let outer = Block::default().borders(Borders::ALL).title(" Probe Details ");
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let mut puzzles: Vec<&Puzzle> = app.puzzles.values().collect();
+    puzzles.sort_by(|a, b| a.name.cmp(&b.name));
+    if puzzles.is_empty() || puzzles.iter().all(|p| !p.probe.has_data()) {
+        let text = "No probe results yet. Enable probing in the [probe] section of the config.";
+        f.render_widget(Paragraph::new(text).alignment(Alignment::Center), inner);
+        return;
+    }
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(puzzles.iter().map(|_| Constraint::Length(3)).collect::<Vec<_>>())
+        .split(inner);
+
+    for (puzzle, row) in puzzles.iter().zip(rows.iter()) {
+        let stats = &puzzle.probe;
+        let ms = |v: Option<u64>| v.map_or("-".to_string(), |v| format!("{}ms", v));
+        let mut title = format!(
+            " {} ({}) | last {} | avg {} | max {} | failures {}/{} ",
+            puzzle.name,
+            puzzle.ip,
+            stats.last_rtt.map_or("-".to_string(), |r| format!("{}ms", r.as_millis())),
+            ms(stats.average_ms()),
+            ms(stats.max_ms()),
+            stats.total_failures,
+            stats.total_probes
+        );
+        if let Some(error) = &stats.last_error {
+            title.push_str(&format!("| {} ", error));
+        }
+        let color = if stats.last_rtt.is_some() { Color::Green } else { Color::Red };
+
+        // Newest samples on the right, cut to the available width
+        let data = stats.sparkline();
+        let data = &data[data.len().saturating_sub(row.width as usize)..];
+        let sparkline = Sparkline::default()
+            .block(Block::default().borders(Borders::TOP).title(title))
+            .data(data)
+            .style(Style::default().fg(color));
+        f.render_widget(sparkline, *row);
+    }
+}
+
+// Overlay for picking a hint: puzzles left, their hints right, given hints below
+fn render_hint_panel(f: &mut Frame, app: &App, panel: &HintPanel) {
+    let area = centered_rect(f.area().width * 4 / 5, f.area().height * 4 / 5, f.area());
+    f.render_widget(Clear, area);
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Hints ({} given) ", app.hints_given.len()))
+        .title_bottom(Line::from(" ←/→ puzzle | ↑/↓ hint | Enter send | h / Esc close ").right_aligned());
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    if app.hints.is_empty() {
+        let text = "No hint catalogue loaded. Set `file` in the [hints] section of the config.";
+        f.render_widget(Paragraph::new(text).alignment(Alignment::Center), inner);
+        return;
+    }
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(8)])
+        .split(inner);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(30), Constraint::Min(0)])
+        .split(rows[0]);
+
+    let given_count = |puzzle: &str| app.hints_given.iter().filter(|h| h.puzzle == puzzle).count();
+    let selected = Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD);
+
+    let puzzle_items: Vec<ListItem> = app
+        .hints
+        .puzzles()
+        .enumerate()
+        .map(|(i, name)| {
+            let item = ListItem::new(format!("🧩 {:<18} {}/{}", name, given_count(name), app.hints.hints(name).len()));
+            if i == panel.puzzle { item.style(selected) } else { item }
+        })
+        .collect();
+    f.render_widget(
+        List::new(puzzle_items).block(Block::default().borders(Borders::RIGHT).title(" Puzzle ")),
+        columns[0],
+    );
+
+    let name = app.hint_puzzle(panel.puzzle).map_or("", String::as_str);
+    let hint_items: Vec<ListItem> = app
+        .hints
+        .hints(name)
+        .iter()
+        .enumerate()
+        .map(|(i, text)| {
+            let given = app.hints_given.iter().any(|h| h.puzzle == name && h.text == *text);
+            let item = ListItem::new(format!(" {} {}. {}", if given { "✓" } else { " " }, i + 1, text));
+            match (i == panel.hint, given) {
+                (true, _) => item.style(selected),
+                (false, true) => item.style(Style::default().fg(Color::DarkGray)),
+                (false, false) => item,
+            }
+        })
+        .collect();
+    f.render_widget(List::new(hint_items).block(Block::default().title(format!(" Hints for {} ", name))), columns[1]);
+
+    // Newest given hints, the latest at the bottom
+    let shown = rows[1].height.saturating_sub(1) as usize;
+    let given_items: Vec<ListItem> = app.hints_given[app.hints_given.len().saturating_sub(shown)..]
+        .iter()
+        .map(|h| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<10} ", h.time_label()), Style::default().fg(Color::DarkGray)),
+                Span::styled(format!("{}: ", h.puzzle), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(h.text.clone()),
+            ]))
+        })
+        .collect();
+    f.render_widget(
+        List::new(given_items).block(Block::default().borders(Borders::TOP).title(" Given this game ")),
+        rows[1],
+    );
+}
+
+// The running game next to the history of past ones
+fn render_game_tab(f: &mut Frame, app: &mut App, area: Rect) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(area);
+    render_current_game(f, app, columns[0]);
+    render_history(f, app, columns[1]);
+}
+
+// Team, solved puzzles and the hints given so far
+fn render_current_game(f: &mut Frame, app: &App, area: Rect) {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = Vec::new();
+    match &app.game {
+        Some(game) => {
+            lines.push(Line::styled(format!(" {} ({} players)", game.team, game.players), bold));
+            lines.push(Line::from(format!(
+                " Started {} · {} {}",
+                game.started.format("%H:%M"),
+                app.clock.display(),
+                app.clock.state_label()
+            )));
+            lines.push(Line::from(""));
+            lines.push(Line::styled(format!(" Puzzles solved ({}/{})", game.solved.len(), app.puzzles.len()), bold));
+            let mut solved: Vec<_> = game.solved.iter().collect();
+            solved.sort_by_key(|s| s.game_secs);
+            lines.extend(solved.iter().map(|s| {
+                Line::from(format!("  @ {}  {}", format_game_time(Duration::from_secs(s.game_secs)), s.puzzle))
+            }));
+        }
+        None => lines.push(Line::styled(" No game running. Start one with g.", dim)),
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::styled(format!(" Hints ({})", app.hints_given.len()), bold));
+    lines.extend(app.hints_given.iter().map(|h| Line::from(format!("  {}  {}: {}", h.time_label(), h.puzzle, h.text))));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Current Game ")
+        .title_bottom(Line::from(" g new | G end | h hints ").right_aligned());
+    f.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+}
+
+// Past games, newest first, with the details of the selected one
+fn render_history(f: &mut Frame, app: &mut App, area: Rect) {
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Game History ({} games) ", app.history.games.len()))
+        .title_bottom(Line::from(" ↑/↓ select | L statistics ").right_aligned());
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    if app.history.games.is_empty() {
+        let text = "No games played yet. Start one with g and end it with G.";
+        f.render_widget(Paragraph::new(text).alignment(Alignment::Center), inner);
+        return;
+    }
+
+    // The list is as wide as its lines, the details get the rest
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(70), Constraint::Min(0)])
+        .split(inner);
+
+    let games: Vec<&GameRecord> = app.history.games.iter().rev().collect();
+    let selected = app.history_selected.min(games.len() - 1);
+    let items: Vec<ListItem> = games
+        .iter()
+        .map(|g| {
+            ListItem::new(Line::from(vec![
+                Span::raw(format!(
+                    "{} {:<16} {:>2}P ",
+                    g.started.format("%m-%d %H:%M"),
+                    g.team.chars().take(16).collect::<String>(),
+                    g.players
+                )),
+                Span::styled(format!("{:<8}", g.result.label()), Style::default().fg(g.result.color())),
+                Span::raw(format!(
+                    " {}  {} solved  {} hints",
+                    format_game_time(Duration::from_secs(g.total_secs)),
+                    g.solved.len(),
+                    g.hints.len()
+                )),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::RIGHT))
+        .highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD));
+    let mut state = ListState::default().with_selected(Some(selected));
+    f.render_stateful_widget(list, columns[0], &mut state);
+    app.regions.history = Some(ListRegion {
+        area: Rect { width: columns[0].width.saturating_sub(1), ..columns[0] },
+        offset: state.offset(),
+        items: (0..games.len()).collect(),
+    });
+
+    let Some(game) = games.get(selected) else { return };
+    let at = |secs: Option<u64>| secs.map_or("--:--".to_string(), |s| format_game_time(Duration::from_secs(s)));
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut lines = vec![
+        Line::styled(format!(" {} ({} players)", game.team, game.players), bold),
+        Line::from(format!(
+            " {} - {}",
+            game.started.format("%Y-%m-%d %H:%M"),
+            game.ended.format("%H:%M")
+        )),
+        Line::from(vec![
+            Span::styled(format!(" {}", game.result.label()), Style::default().fg(game.result.color()).add_modifier(Modifier::BOLD)),
+            Span::raw(format!(" after {}", at(Some(game.total_secs)))),
+        ]),
+        Line::from(""),
+        Line::styled(format!(" Puzzles solved ({})", game.solved.len()), bold),
+    ];
+    let mut solved: Vec<_> = game.solved.iter().collect();
+    solved.sort_by_key(|s| s.game_secs);
+    lines.extend(solved.iter().map(|s| Line::from(format!("  @ {}  {}", at(Some(s.game_secs)), s.puzzle))));
+    lines.push(Line::from(""));
+    lines.push(Line::styled(format!(" Hints ({})", game.hints.len()), bold));
+    lines.extend(game.hints.iter().map(|h| Line::from(format!("  @ {}  {}: {}", at(h.game_secs), h.puzzle, h.text))));
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), columns[1]);
+}
+
+// Overlay with the leaderboard and per-puzzle and per-time statistics
+fn render_stats(f: &mut Frame, stats: &Stats) {
+    let area = centered_rect(f.area().width * 4 / 5, f.area().height * 4 / 5, f.area());
+    f.render_widget(Clear, area);
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(" Statistics ")
+        .title_bottom(Line::from(" L / Esc close ").right_aligned());
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    if stats.games == 0 {
+        let text = "No games played yet. Statistics are computed from the game history (g / G).";
+        f.render_widget(Paragraph::new(text).alignment(Alignment::Center), inner);
+        return;
+    }
+
+    let mmss = |d: Option<Duration>| d.map_or("--:--".to_string(), format_game_time);
+    let dim = Style::default().fg(Color::DarkGray);
+    let rate = SuccessRate { label: String::new(), games: stats.games, escaped: stats.escaped };
+    let mut summary = vec![Span::raw(format!(
+        " {} games · {} escaped ({}%) · average escape time {}",
+        stats.games,
+        stats.escaped,
+        rate.percent(),
+        mmss(stats.average_escape)
+    ))];
+    if let Some(name) = &stats.most_blocking {
+        summary.push(Span::raw(" · most blocking: "));
+        summary.push(Span::styled(name.clone(), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)));
+    }
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Percentage(55), Constraint::Min(5)])
+        .split(inner);
+    f.render_widget(Paragraph::new(Line::from(summary)), rows[0]);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[1]);
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[2]);
+
+    // Best times
+    let mut lines = vec![Line::styled(
+        format!(" {:<3} {:<16} {:>3} {:>6} {:>5}  {}", "#", "Team", "P", "Time", "Hints", "Date"),
+        dim,
+    )];
+    lines.extend(stats.leaderboard.iter().enumerate().map(|(i, best)| {
+        let style = if i == 0 { Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD) } else { Style::default() };
+        Line::styled(
+            format!(
+                " {:<3} {:<16} {:>3} {:>6} {:>5}  {}",
+                i + 1,
+                best.team.chars().take(16).collect::<String>(),
+                best.players,
+                format_game_time(best.total),
+                best.hints,
+                best.date
+            ),
+            style,
+        )
+    }));
+    if stats.leaderboard.is_empty() {
+        lines.push(Line::styled(" No team has escaped yet", dim));
+    }
+    f.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::TOP | Borders::RIGHT).title(" Best Times ")),
+        top[0],
     );
-}
\ No newline at end of file
+
+    // Puzzles
+    let mut lines = vec![Line::styled(
+        format!(" {:<16} {:>7} {:>9} {:>7} {:>7} {:>5}", "Puzzle", "Solved", "Avg at", "Took", "Blocked", "Hints"),
+        dim,
+    )];
+    lines.extend(stats.puzzles.iter().map(|p| {
+        let style = if stats.most_blocking.as_ref() == Some(&p.name) {
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        Line::styled(
+            format!(
+                " {:<16} {:>7} {:>9} {:>7} {:>7} {:>5}",
+                p.name.chars().take(16).collect::<String>(),
+                format!("{}/{}", p.solved, p.played),
+                mmss(p.average_solved_at),
+                mmss(p.average_taken),
+                p.blocked,
+                p.hints
+            ),
+            style,
+        )
+    }));
+    f.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::TOP).title(" Puzzles ")),
+        top[1],
+    );
+
+    f.render_widget(success_rates(&stats.weekdays, " Success by Weekday ", Borders::TOP | Borders::RIGHT), bottom[0]);
+    f.render_widget(success_rates(&stats.slots, " Success by Start Time ", Borders::TOP), bottom[1]);
+}
+
+// One line per weekday or time slot with a bar for the escape rate
+fn success_rates<'a>(rates: &[SuccessRate], title: &'a str, borders: Borders) -> Paragraph<'a> {
+    const BAR: usize = 20;
+    let lines: Vec<Line> = rates
+        .iter()
+        .map(|r| {
+            let filled = r.percent() as usize * BAR / 100;
+            let color = match r.percent() {
+                67.. => Color::Green,
+                34..=66 => Color::Yellow,
+                _ => Color::Red,
+            };
+            Line::from(vec![
+                Span::raw(format!(" {:<7} {:>3} games {:>3}/{:<3} ", r.label, r.games, r.escaped, r.games)),
+                Span::styled("█".repeat(filled), Style::default().fg(color)),
+                Span::styled("░".repeat(BAR - filled), Style::default().fg(Color::DarkGray)),
+                Span::raw(format!(" {:>3}%", r.percent())),
+            ])
+        })
+        .collect();
+    Paragraph::new(lines).block(Block::default().borders(borders).title(title))
+}
+
+// The load history: CPU, RAM, server memory and log rate
+fn render_system(f: &mut Frame, app: &App, area: Rect) {
+    let window = app.metrics.window();
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" System (last {} min) ", window.as_secs() / 60));
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(inner);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[0]);
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[1]);
+
+    let metrics = &app.metrics;
+    let cpu = metrics.points(|s| Some(s.cpu as f64));
+    let server_cpu = metrics.points(|s| s.server_cpu.map(f64::from));
+    let ram = metrics.points(|s| Some(s.ram_mb as f64));
+    let rss = metrics.points(|s| s.server_rss_mb.map(|v| v as f64));
+    let log_rate = metrics.points(|s| Some(s.log_lines_per_sec));
+
+    let peak = |points: &[(f64, f64)]| points.iter().map(|(_, y)| *y).fold(0.0, f64::max);
+    let last = |points: &[(f64, f64)], unit: &str| {
+        points.last().map_or("-".to_string(), |(_, y)| format!("{:.1}{}", y, unit))
+    };
+
+    let title = format!(" CPU %  system {} · server {} ", last(&cpu, "%"), last(&server_cpu, "%"));
+    let datasets = vec![dataset("system", Color::Cyan, &cpu), dataset("server", Color::Yellow, &server_cpu)];
+    f.render_widget(history_chart(title, datasets, window, peak(&server_cpu).max(100.0)), top[0]);
+
+    let used = ram.last().map_or("-".to_string(), |(_, y)| format!("{:.0}", y));
+    let title = format!(" RAM  {} / {} MB ", used, app.total_ram);
+    let datasets = vec![dataset("used", Color::Cyan, &ram)];
+    f.render_widget(history_chart(title, datasets, window, (app.total_ram as f64).max(1.0)), top[1]);
+
+    let title = format!(" Server RSS  {} (peak {:.1} MB) ", last(&rss, " MB"), peak(&rss));
+    let datasets = vec![dataset("rss", Color::Yellow, &rss)];
+    f.render_widget(history_chart(title, datasets, window, (peak(&rss) * 1.2).max(10.0)), bottom[0]);
+
+    let title = format!(" Log lines/s  {} (peak {:.1}) ", last(&log_rate, ""), peak(&log_rate));
+    let datasets = vec![dataset("lines/s", Color::Green, &log_rate)];
+    f.render_widget(history_chart(title, datasets, window, (peak(&log_rate) * 1.2).max(5.0)), bottom[1]);
+}
+
+fn dataset<'a>(name: &'a str, color: Color, points: &'a [(f64, f64)]) -> Dataset<'a> {
+    Dataset::default()
+        .name(name)
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(color))
+        .data(points)
+}
+
+// Time runs from `-window` on the left to now on the right
+fn history_chart<'a>(title: String, datasets: Vec<Dataset<'a>>, window: Duration, y_max: f64) -> Chart<'a> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let mins = window.as_secs() / 60;
+    // A single line needs no legend, its title says what it is
+    let legend = (datasets.len() > 1).then_some(LegendPosition::TopLeft);
+    Chart::new(datasets)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .legend_position(legend)
+        .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)))
+        .x_axis(
+            Axis::default()
+                .bounds([-window.as_secs_f64(), 0.0])
+                .style(dim)
+                .labels([format!("-{}m", mins), format!("-{}m", mins / 2), "now".to_string()]),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, y_max])
+                .style(dim)
+                .labels(["0".to_string(), format!("{:.0}", y_max / 2.0), format!("{:.0}", y_max)]),
+        )
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app() -> App {
+        let mut config = Config::default();
+        config.logs.save = false;
+        config.recording.enabled = false;
+        config.history.save = false;
+        App::new(&config).unwrap()
+    }
+
+    #[test]
+    fn chained_traceback_is_one_entry_per_stream() {
+        let mut app = app();
+        let stderr = [
+            TRACEBACK_START,
+            r#"  File "server.py", line 12, in handle"#,
+            "KeyError: 'safe'",
+            "",
+            "During handling of the above exception, another exception occurred:",
+            "",
+            TRACEBACK_START,
+            r#"  File "server.py", line 14, in handle"#,
+            "RuntimeError: unknown puzzle",
+            "INFO next request",
+        ];
+        for (i, line) in stderr.iter().enumerate() {
+            app.process_log(line.to_string(), true);
+            // Stdout in between neither ends nor joins the traceback
+            if i == 1 {
+                app.process_log("INFO stdout line".to_string(), false);
+            }
+        }
+
+        let texts: Vec<String> = app.logs.iter_from(0).map(|(_, e)| e.text.clone()).collect();
+        assert_eq!(texts, [TRACEBACK_START, "INFO stdout line", "INFO next request"]);
+        let traceback = app.logs.get(0).unwrap();
+        assert_eq!(traceback.level, Some(LogLevel::Error));
+        assert_eq!(traceback.traceback.len(), 8);
+        assert_eq!(traceback.traceback.last().unwrap(), "RuntimeError: unknown puzzle");
+    }
+
+
+    #[test]
+    fn finished_game_keeps_its_hints_and_time() {
+        let _time = timebase::test_lock();
+        let (instant, local) = (std::time::Instant::now(), Local::now());
+        timebase::set(instant, local);
+        let mut app = app();
+        app.start_game("Die Füchse".to_string(), 4);
+        timebase::set(instant + Duration::from_secs(125), local + chrono::TimeDelta::seconds(125));
+        app.hint_delivered("safe".to_string(), "Das Bild".to_string());
+        app.hint_delivered("safe".to_string(), "Die Zahlen".to_string());
+
+        // The clock cannot be reset under the running game
+        app.confirm_reset_clock();
+        assert!(app.pending_confirm.is_none());
+        assert_eq!(app.hints_given.len(), 2);
+        assert_eq!(app.clock.elapsed(), Duration::from_secs(125));
+
+        app.end_game(GameResult::Escaped);
+        app.confirm_reset_clock();
+        assert!(matches!(app.pending_confirm, Some(Confirm::ResetClock)));
+        app.reset_clock();
+        assert!(app.hints_given.is_empty());
+
+        let game = &app.history.games[0];
+        assert_eq!(game.total_secs, 125);
+        assert_eq!(game.hints.len(), 2);
+        assert_eq!(game.hints[1].game_secs, Some(125));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::config::MetricsConfig;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+// --- Resource History ---
+//
+// One sample per `sample_secs`, the last `history_mins` are kept. Samples are
+// also recorded, so a replay shows the load of the original session.
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Sample {
+    pub cpu: f32,
+    pub ram_mb: u64,
+    // `None` while the server is not running
+    pub server_cpu: Option<f32>,
+    pub server_rss_mb: Option<u64>,
+    pub log_lines_per_sec: f64,
+}
+
+pub struct Metrics {
+    pub samples: VecDeque<Sample>,
+    pub interval: Duration,
+    capacity: usize,
+    last_sample: Option<Instant>,
+    // Log length at the last sample, for the lines per second
+    last_log_len: usize,
+}
+
+impl Metrics {
+    pub fn new(config: &MetricsConfig) -> Self {
+        let interval = Duration::from_secs(config.sample_secs);
+        Self {
+            samples: VecDeque::new(),
+            interval,
+            capacity: (config.history_mins * 60 / config.sample_secs) as usize,
+            last_sample: None,
+            last_log_len: 0,
+        }
+    }
+
+    // Length of the history the charts span
+    pub fn window(&self) -> Duration {
+        self.interval * self.capacity as u32
+    }
+
+    // Returns the number of new log lines per second once a sample is due
+    pub fn due(&mut self, log_len: usize) -> Option<f64> {
+        let now = Instant::now();
+        if self.last_sample.is_some_and(|t| now.duration_since(t) < self.interval) {
+            return None;
+        }
+        let elapsed = self.last_sample.map_or(self.interval, |t| now.duration_since(t));
+        let lines = log_len.saturating_sub(self.last_log_len);
+        self.last_sample = Some(now);
+        self.last_log_len = log_len;
+        Some(lines as f64 / elapsed.as_secs_f64())
+    }
+
+    pub fn push(&mut self, sample: Sample) {
+        self.samples.push_back(sample);
+        while self.samples.len() > self.capacity {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.last_sample = None;
+        self.last_log_len = 0;
+    }
+
+    // Chart points, x in seconds before the newest sample (0 is now)
+    pub fn points(&self, value: impl Fn(&Sample) -> Option<f64>) -> Vec<(f64, f64)> {
+        let newest = self.samples.len().saturating_sub(1);
+        self.samples
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| {
+                let age = (newest - i) as f64 * self.interval.as_secs_f64();
+                value(s).map(|v| (-age, v))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(history_mins: u64, sample_secs: u64) -> Metrics {
+        Metrics::new(&MetricsConfig { history_mins, sample_secs })
+    }
+
+    fn sample(cpu: f32, server_cpu: Option<f32>) -> Sample {
+        Sample { cpu, ram_mb: 0, server_cpu, server_rss_mb: None, log_lines_per_sec: 0.0 }
+    }
+
+    #[test]
+    fn sample_is_due_once_per_interval() {
+        let mut metrics = metrics(15, 2);
+        assert_eq!(metrics.window(), Duration::from_secs(900));
+
+        // The first sample counts the lines so far over one interval
+        assert_eq!(metrics.due(10), Some(5.0));
+        assert_eq!(metrics.due(20), None);
+
+        metrics.last_sample = Instant::now().checked_sub(Duration::from_secs(4));
+        let rate = metrics.due(30).unwrap();
+        assert!((rate - 5.0).abs() < 0.1, "{}", rate);
+
+        metrics.clear();
+        assert_eq!(metrics.due(4), Some(2.0));
+    }
+
+    #[test]
+    fn only_the_history_window_is_kept() {
+        let mut metrics = metrics(1, 20);
+        for cpu in [1.0, 2.0, 3.0, 4.0, 5.0] {
+            metrics.push(sample(cpu, (cpu != 4.0).then_some(cpu * 10.0)));
+        }
+        assert_eq!(metrics.samples.len(), 3);
+        assert_eq!(metrics.points(|s| Some(s.cpu as f64)), [(-40.0, 3.0), (-20.0, 4.0), (0.0, 5.0)]);
+
+        // Samples without a value leave a gap
+        assert_eq!(metrics.points(|s| s.server_cpu.map(f64::from)), [(-40.0, 30.0), (0.0, 50.0)]);
+    }
+}
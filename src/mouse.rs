@@ -0,0 +1,184 @@
+use crate::{tabs::Tab, App};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+
+// --- Click Regions ---
+//
+// Every frame notes where it drew the clickable parts, a mouse event is
+// looked up in the regions of the frame the user is looking at.
+
+// Log lines moved per wheel notch
+const WHEEL_LINES: usize = 3;
+
+// The rows of a list (inside its border) and what each row stands for
+pub struct ListRegion<T> {
+    pub area: Rect,
+    // First item shown, rows before it are scrolled away
+    pub offset: usize,
+    pub items: Vec<T>,
+}
+
+impl<T> ListRegion<T> {
+    fn item_at(&self, position: Position) -> Option<&T> {
+        if !self.area.contains(position) {
+            return None;
+        }
+        self.items.get(self.offset + (position.y - self.area.y) as usize)
+    }
+}
+
+#[derive(Default)]
+pub struct Regions {
+    pub tabs: Vec<(Rect, Tab)>,
+    pub alert: Option<Rect>,
+    pub logs: Option<Rect>,
+    // The column of the scrollbar, its arrows in the first and last row
+    pub log_scrollbar: Option<Rect>,
+    pub puzzles: Option<ListRegion<String>>,
+    // The "recent" separator is `None`
+    pub clients: Option<ListRegion<Option<String>>>,
+    pub history: Option<ListRegion<usize>>,
+    pub puzzle_detail_logs: Option<Rect>,
+    // Confirmation popup and its buttons, each sent as the key it stands for
+    pub popup: Option<Rect>,
+    pub buttons: Vec<(Rect, KeyCode)>,
+}
+
+fn hit(area: Option<Rect>, position: Position) -> bool {
+    area.is_some_and(|a| a.contains(position))
+}
+
+// Entry of `len` a row of the scrollbar stands for. The track lies between the
+// arrows, its top is the oldest entry; rows past its end give `len`, the newest.
+fn scrollbar_position(scrollbar: Rect, row: u16, len: usize) -> usize {
+    let track_top = scrollbar.y + 1;
+    let track_len = scrollbar.height.saturating_sub(2).max(1) as usize;
+    let offset = (row.saturating_sub(track_top) as usize).min(track_len);
+    offset * len / track_len
+}
+
+// --- Mouse Events ---
+
+impl App {
+    // Most clicks act right away, a click on a button comes back as its key
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> Option<KeyEvent> {
+        let position = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => return self.click(position),
+            MouseEventKind::Drag(MouseButton::Left) if self.dragging_scrollbar => self.drag_scrollbar(position.y),
+            MouseEventKind::Up(_) => self.dragging_scrollbar = false,
+            MouseEventKind::ScrollUp => self.wheel(position, false),
+            MouseEventKind::ScrollDown => self.wheel(position, true),
+            _ => {}
+        }
+        None
+    }
+
+    fn click(&mut self, position: Position) -> Option<KeyEvent> {
+        // The confirmation is modal: a button answers it, a click outside cancels
+        if self.pending_confirm.is_some() {
+            if let Some((_, code)) = self.regions.buttons.iter().find(|(area, _)| area.contains(position)) {
+                return Some(KeyEvent::from(*code));
+            }
+            return (!hit(self.regions.popup, position)).then(|| KeyEvent::from(KeyCode::Esc));
+        }
+        // Prompts and overlays are left to the keyboard
+        if self.prompt.is_some() || self.hint_panel.is_some() || self.stats.is_some() {
+            return None;
+        }
+
+        if let Some((_, tab)) = self.regions.tabs.iter().find(|(area, _)| area.contains(position)) {
+            let tab = *tab;
+            self.switch_tab(tab);
+        } else if hit(self.regions.alert, position) {
+            self.alert = None;
+        } else if let Some(scrollbar) = self.regions.log_scrollbar
+            && scrollbar.contains(position)
+        {
+            if position.y == scrollbar.y {
+                self.scroll_up();
+            } else if position.y == scrollbar.bottom() - 1 {
+                self.scroll_down();
+            } else {
+                self.dragging_scrollbar = true;
+                self.drag_scrollbar(position.y);
+            }
+        } else if let Some(name) = self.regions.puzzles.as_ref().and_then(|r| r.item_at(position)) {
+            let name = name.clone();
+            self.switch_tab(Tab::Puzzles);
+            self.open_puzzle_detail(name);
+        } else if let Some(ip) = self.regions.clients.as_ref().and_then(|r| r.item_at(position)) {
+            if ip.is_some() {
+                self.client_selected = ip.clone();
+            }
+        } else if let Some(index) = self.regions.history.as_ref().and_then(|r| r.item_at(position)) {
+            self.history_selected = *index;
+        }
+        None
+    }
+
+    fn drag_scrollbar(&mut self, row: u16) {
+        let Some(scrollbar) = self.regions.log_scrollbar else { return };
+        self.scroll_to(scrollbar_position(scrollbar, row, self.log_view.len()));
+    }
+
+    fn wheel(&mut self, position: Position, down: bool) {
+        if self.pending_confirm.is_some() || self.hint_panel.is_some() || self.stats.is_some() {
+            return;
+        }
+        if hit(self.regions.logs, position) {
+            let start = self.log_view_start;
+            self.scroll_to(if down { start + WHEEL_LINES } else { start.saturating_sub(WHEEL_LINES) });
+        } else if hit(self.regions.puzzle_detail_logs, position)
+            && let Some(detail) = &mut self.puzzle_detail
+        {
+            // The offset counts the lines hidden below, so the wheel works the other way round
+            let last = detail.log_view.len().saturating_sub(detail.log_height);
+            detail.log_offset = if down {
+                detail.log_offset.saturating_sub(WHEEL_LINES)
+            } else {
+                (detail.log_offset + WHEEL_LINES).min(last)
+            };
+        } else if self.regions.history.as_ref().is_some_and(|r| r.area.contains(position)) {
+            self.handle_game_key(if down { KeyCode::Down } else { KeyCode::Up });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_rows_map_to_items() {
+        let list = ListRegion { area: Rect::new(2, 5, 20, 3), offset: 1, items: vec!["a", "b", "c", "d"] };
+        assert_eq!(list.item_at(Position::new(2, 5)), Some(&"b"));
+        assert_eq!(list.item_at(Position::new(21, 7)), Some(&"d"));
+        // Outside the area, also left of it and in the row below
+        assert_eq!(list.item_at(Position::new(1, 5)), None);
+        assert_eq!(list.item_at(Position::new(22, 5)), None);
+        assert_eq!(list.item_at(Position::new(2, 8)), None);
+
+        // Rows below the last item
+        let short = ListRegion { area: Rect::new(0, 0, 10, 5), offset: 0, items: vec!["a"] };
+        assert_eq!(short.item_at(Position::new(0, 1)), None);
+    }
+
+    #[test]
+    fn scrollbar_rows_spread_over_the_entries() {
+        // Arrows in rows 10 and 21, the track in between is 10 rows long
+        let scrollbar = Rect::new(79, 10, 1, 12);
+        assert_eq!(scrollbar_position(scrollbar, 11, 200), 0);
+        assert_eq!(scrollbar_position(scrollbar, 12, 200), 20);
+        assert_eq!(scrollbar_position(scrollbar, 16, 200), 100);
+        assert_eq!(scrollbar_position(scrollbar, 20, 200), 180);
+        // Dragged past either end
+        assert_eq!(scrollbar_position(scrollbar, 0, 200), 0);
+        assert_eq!(scrollbar_position(scrollbar, 40, 200), 200);
+        assert_eq!(scrollbar_position(scrollbar, 16, 0), 0);
+
+        // A bar too short for a track still works
+        assert_eq!(scrollbar_position(Rect::new(0, 0, 1, 2), 1, 50), 0);
+        assert_eq!(scrollbar_position(Rect::new(0, 0, 1, 2), 2, 50), 50);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{
+    config::{ProbeConfig, ProbeMode},
+    App,
+};
+use std::{
+    collections::VecDeque,
+    io::{Read, Write},
+    net::{IpAddr, SocketAddr, TcpStream, UdpSocket},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+// --- Probe Results ---
+
+#[derive(Default)]
+pub struct ProbeStats {
+    pub last_rtt: Option<Duration>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+    pub total_failures: u32,
+    pub total_probes: u32,
+    // Round trip times in ms, failures are recorded as `None`
+    pub history: VecDeque<Option<u64>>,
+}
+
+impl ProbeStats {
+    fn record(&mut self, result: Result<Duration, String>, history_len: usize) {
+        self.total_probes += 1;
+        let sample = match result {
+            Ok(rtt) => {
+                self.last_rtt = Some(rtt);
+                self.last_error = None;
+                self.consecutive_failures = 0;
+                Some(rtt.as_millis() as u64)
+            }
+            Err(e) => {
+                self.last_rtt = None;
+                self.last_error = Some(e);
+                self.consecutive_failures += 1;
+                self.total_failures += 1;
+                None
+            }
+        };
+
+        self.history.push_back(sample);
+        while self.history.len() > history_len {
+            self.history.pop_front();
+        }
+    }
+
+    pub fn has_data(&self) -> bool {
+        self.total_probes > 0
+    }
+
+    // Short text for the puzzle list: "12ms" or "✗3"
+    pub fn summary(&self) -> String {
+        match self.last_rtt {
+            Some(rtt) => format!("{}ms", rtt.as_millis()),
+            None => format!("✗{}", self.consecutive_failures),
+        }
+    }
+
+    // Sparkline data, failures drawn as empty bars
+    pub fn sparkline(&self) -> Vec<u64> {
+        self.history.iter().map(|s| s.unwrap_or(0)).collect()
+    }
+
+    pub fn average_ms(&self) -> Option<u64> {
+        let ok: Vec<u64> = self.history.iter().flatten().copied().collect();
+        (!ok.is_empty()).then(|| ok.iter().sum::<u64>() / ok.len() as u64)
+    }
+
+    pub fn max_ms(&self) -> Option<u64> {
+        self.history.iter().flatten().copied().max()
+    }
+}
+
+// --- Prober Thread ---
+
+pub fn spawn(config: ProbeConfig, app: Arc<Mutex<App>>) {
+    if !config.enabled {
+        return;
+    }
+
+    thread::spawn(move || {
+        loop {
+            let targets: Vec<(String, IpAddr)> = {
+                let app = app.lock().unwrap();
+                app.puzzles.values()
+                    .filter_map(|p| Some((p.name.clone(), p.ip.parse().ok()?)))
+                    .collect()
+            };
+
+            // Probe all puzzles in parallel so one dead puzzle does not delay the others
+            let results: Vec<(String, Result<Duration, String>)> = thread::scope(|scope| {
+                let handles: Vec<_> = targets.into_iter()
+                    .map(|(name, ip)| {
+                        let config = &config;
+                        scope.spawn(move || (name, probe(config, ip)))
+                    })
+                    .collect();
+                handles.into_iter().filter_map(|h| h.join().ok()).collect()
+            });
+
+            {
+                let mut app = app.lock().unwrap();
+                for (name, result) in results {
+                    if let Some(puzzle) = app.puzzles.get_mut(&name) {
+                        puzzle.probe.record(result, config.history);
+                    }
+                }
+            }
+
+            thread::sleep(Duration::from_secs(config.interval_secs));
+        }
+    });
+}
+
+fn probe(config: &ProbeConfig, ip: IpAddr) -> Result<Duration, String> {
+    let addr = SocketAddr::new(ip, config.port);
+    let timeout = Duration::from_millis(config.timeout_ms);
+    let start = Instant::now();
+
+    match config.mode {
+        ProbeMode::Tcp => {
+            TcpStream::connect_timeout(&addr, timeout).map_err(|e| e.to_string())?;
+        }
+        ProbeMode::Udp => {
+            let bind = if ip.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+            let socket = UdpSocket::bind(bind).map_err(|e| e.to_string())?;
+            socket.set_read_timeout(Some(timeout)).map_err(|e| e.to_string())?;
+            socket.connect(addr).map_err(|e| e.to_string())?;
+            socket.send(config.payload.as_bytes()).map_err(|e| e.to_string())?;
+            let mut buf = [0u8; 512];
+            socket.recv(&mut buf).map_err(|e| e.to_string())?;
+        }
+        ProbeMode::Http => {
+            let mut stream = TcpStream::connect_timeout(&addr, timeout).map_err(|e| e.to_string())?;
+            stream.set_read_timeout(Some(timeout)).map_err(|e| e.to_string())?;
+            stream.set_write_timeout(Some(timeout)).map_err(|e| e.to_string())?;
+            let request = format!(
+                "GET {} HTTP/1.0\r\nHost: {}\r\nConnection: close\r\n\r\n",
+                config.path, ip
+            );
+            stream.write_all(request.as_bytes()).map_err(|e| e.to_string())?;
+
+            // Only the status line matters: "HTTP/1.0 200 OK"
+            let mut buf = [0u8; 64];
+            let n = stream.read(&mut buf).map_err(|e| e.to_string())?;
+            let head = String::from_utf8_lossy(&buf[..n]);
+            let status = head.split_whitespace().nth(1).unwrap_or("");
+            if !(status.starts_with('2') || status.starts_with('3')) {
+                return Err(format!("HTTP status {}", if status.is_empty() { "missing" } else { status }));
+            }
+        }
+    }
+
+    Ok(start.elapsed())
+}
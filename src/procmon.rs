@@ -0,0 +1,194 @@
+use crate::config::ProcessConfig;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+// --- Server Process Stats ---
+
+// Totals over the server process and everything it spawned
+#[derive(Clone, Copy)]
+pub struct ProcessStats {
+    pub pid: u32,
+    // Descendants, not counting the server itself
+    pub children: usize,
+    // Percent of one core, can exceed 100 on several cores
+    pub cpu: f32,
+    pub rss: u64,
+    pub threads: usize,
+    pub open_files: Option<usize>,
+    pub uptime: Duration,
+}
+
+impl ProcessStats {
+    pub fn rss_mb(&self) -> u64 {
+        self.rss / 1024 / 1024
+    }
+}
+
+// Which values are over their `[process]` threshold
+pub struct Warnings {
+    pub cpu: bool,
+    pub rss: bool,
+    pub threads: bool,
+    pub open_files: bool,
+}
+
+impl Warnings {
+    // A threshold of 0 never warns
+    pub fn check(config: &ProcessConfig, stats: &ProcessStats) -> Self {
+        let over = |value: u64, limit: u64| limit > 0 && value >= limit;
+        Self {
+            cpu: config.warn_cpu_percent > 0.0 && stats.cpu >= config.warn_cpu_percent,
+            rss: over(stats.rss_mb(), config.warn_rss_mb),
+            threads: over(stats.threads as u64, config.warn_threads),
+            open_files: stats.open_files.is_some_and(|n| over(n as u64, config.warn_open_files)),
+        }
+    }
+}
+
+// --- Process Monitor ---
+
+// Looks up the server and its descendants in the process table. Reading all of
+// /proc is not free, so it refreshes at most every `interval_ms`.
+pub struct ProcessMonitor {
+    sys: System,
+    interval: Duration,
+    last_refresh: Option<Instant>,
+    stats: Option<ProcessStats>,
+}
+
+impl ProcessMonitor {
+    pub fn new(config: &ProcessConfig) -> Self {
+        Self {
+            sys: System::new(),
+            interval: Duration::from_millis(config.interval_ms),
+            last_refresh: None,
+            stats: None,
+        }
+    }
+
+    // `pid` of the running server, `None` while it is stopped
+    pub fn refresh(&mut self, pid: Option<u32>) -> Option<ProcessStats> {
+        let Some(pid) = pid else {
+            self.stats = None;
+            return None;
+        };
+        if self.stats.is_some_and(|s| s.pid == pid) && self.last_refresh.is_some_and(|t| t.elapsed() < self.interval) {
+            return self.stats;
+        }
+
+        self.sys.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_cpu().with_memory().with_tasks(),
+        );
+        self.last_refresh = Some(Instant::now());
+        self.stats = self.collect(Pid::from_u32(pid));
+        self.stats
+    }
+
+    fn collect(&self, root: Pid) -> Option<ProcessStats> {
+        let server = self.sys.process(root)?;
+
+        // Threads show up as processes too, only real processes count as children
+        let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+        for (pid, process) in self.sys.processes() {
+            if process.thread_kind().is_none()
+                && let Some(parent) = process.parent()
+            {
+                children.entry(parent).or_default().push(*pid);
+            }
+        }
+
+        let mut stats = ProcessStats {
+            pid: root.as_u32(),
+            children: 0,
+            cpu: 0.0,
+            rss: 0,
+            threads: 0,
+            open_files: Some(0),
+            uptime: Duration::from_secs(server.run_time()),
+        };
+        let mut pending = vec![root];
+        while let Some(pid) = pending.pop() {
+            let Some(process) = self.sys.process(pid) else { continue };
+            if pid != root {
+                stats.children += 1;
+            }
+            stats.cpu += process.cpu_usage();
+            stats.rss += process.memory();
+            // `tasks` lists the threads besides the main one
+            stats.threads += process.tasks().map_or(1, |t| t.len() + 1);
+            stats.open_files = stats.open_files.zip(process.open_files()).map(|(a, b)| a + b);
+            pending.extend(children.get(&pid).into_iter().flatten());
+        }
+        Some(stats)
+    }
+}
+
+// "3d 4h", "2h 05m", "4m 12s"
+pub fn format_uptime(uptime: Duration) -> String {
+    let secs = uptime.as_secs();
+    match secs {
+        0..3600 => format!("{}m {:02}s", secs / 60, secs % 60),
+        3600..86400 => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+        _ => format!("{}d {}h", secs / 86400, secs % 86400 / 3600),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(cpu: f32, rss_mb: u64, threads: usize, open_files: Option<usize>) -> ProcessStats {
+        ProcessStats { pid: 1, children: 0, cpu, rss: rss_mb * 1024 * 1024, threads, open_files, uptime: Duration::ZERO }
+    }
+
+    #[test]
+    fn uptime_shows_the_two_largest_units() {
+        assert_eq!(format_uptime(Duration::from_secs(0)), "0m 00s");
+        assert_eq!(format_uptime(Duration::from_secs(252)), "4m 12s");
+        assert_eq!(format_uptime(Duration::from_secs(3599)), "59m 59s");
+        assert_eq!(format_uptime(Duration::from_secs(3600)), "1h 00m");
+        assert_eq!(format_uptime(Duration::from_secs(2 * 3600 + 5 * 60 + 59)), "2h 05m");
+        assert_eq!(format_uptime(Duration::from_secs(86400)), "1d 0h");
+        assert_eq!(format_uptime(Duration::from_secs(3 * 86400 + 4 * 3600 + 59 * 60)), "3d 4h");
+    }
+
+    #[test]
+    fn warnings_start_at_the_threshold() {
+        let config = ProcessConfig::default();
+        let below = Warnings::check(&config, &stats(79.9, 511, 99, Some(511)));
+        assert!(!below.cpu && !below.rss && !below.threads && !below.open_files);
+        let at = Warnings::check(&config, &stats(80.0, 512, 100, Some(512)));
+        assert!(at.cpu && at.rss && at.threads && at.open_files);
+
+        // Unknown open files never warn
+        assert!(!Warnings::check(&config, &stats(0.0, 0, 1, None)).open_files);
+    }
+
+    #[test]
+    fn zero_turns_a_warning_off() {
+        let config = ProcessConfig {
+            interval_ms: 1000,
+            warn_cpu_percent: 0.0,
+            warn_rss_mb: 0,
+            warn_threads: 0,
+            warn_open_files: 0,
+        };
+        let warnings = Warnings::check(&config, &stats(0.0, 0, 0, Some(0)));
+        assert!(!warnings.cpu && !warnings.rss && !warnings.threads && !warnings.open_files);
+    }
+
+    #[test]
+    fn own_process_is_found() {
+        let mut monitor = ProcessMonitor::new(&ProcessConfig::default());
+        assert!(monitor.refresh(None).is_none());
+        let stats = monitor.refresh(Some(std::process::id())).unwrap();
+        assert_eq!(stats.pid, std::process::id());
+        assert!(stats.threads >= 1);
+        assert!(stats.rss > 0);
+    }
+}
@@ -0,0 +1,340 @@
+use crate::{clock::format_game_time, probe::ProbeStats, pyliteral, timebase};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Local};
+use ratatui::style::Color;
+use serde_json::{Map, Value};
+use std::{collections::BTreeMap, time::Duration};
+
+// State changes kept per puzzle for the detail pane
+const STATE_HISTORY: usize = 100;
+
+// --- Puzzle State ---
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleState {
+    Registered,
+    Active,
+    Solved,
+    Reset,
+    Error,
+}
+
+impl PuzzleState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PuzzleState::Registered => "REGISTERED",
+            PuzzleState::Active => "ACTIVE",
+            PuzzleState::Solved => "SOLVED",
+            PuzzleState::Reset => "RESET",
+            PuzzleState::Error => "ERROR",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            PuzzleState::Registered => Color::Gray,
+            PuzzleState::Active => Color::Cyan,
+            PuzzleState::Solved => Color::Green,
+            PuzzleState::Reset => Color::Yellow,
+            PuzzleState::Error => Color::Red,
+        }
+    }
+}
+
+// How recently we heard from a puzzle, independent of its game state
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Liveness {
+    Online,
+    Stale,
+    Offline,
+}
+
+impl Liveness {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Liveness::Online => "ONLINE",
+            Liveness::Stale => "STALE",
+            Liveness::Offline => "OFFLINE",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Liveness::Online => Color::Green,
+            Liveness::Stale => Color::Yellow,
+            Liveness::Offline => Color::Red,
+        }
+    }
+}
+
+// What a registration says about the puzzle besides its name
+pub enum Registration {
+    // The dict literal of a plain line, see `registration_dict`
+    Dict(String),
+    // The other fields of a JSON `puzzle_registered` event
+    Fields(Map<String, Value>),
+}
+
+// One entry of a puzzle's state history
+pub struct StateChange {
+    pub state: PuzzleState,
+    pub at: DateTime<Local>,
+    pub game_time: Option<Duration>,
+}
+
+pub struct Puzzle {
+    pub name: String,
+    pub ip: String,
+    // Latest registration, `None` if the puzzle only showed up in other lines
+    pub registered_at: Option<DateTime<Local>>,
+    // That registration as printed by the server, the dict or the JSON fields
+    pub registration: Option<String>,
+    // Everything in it, e.g. fw, port, mac, type
+    pub attributes: BTreeMap<String, Value>,
+    pub last_seen: DateTime<Local>,
+    // Set while the puzzle is reported as offline, so the event is logged once
+    pub reported_offline: bool,
+    pub state: PuzzleState,
+    // Oldest first, at most `STATE_HISTORY` entries
+    pub history: Vec<StateChange>,
+    // Wall clock and game time of the last solve, cleared on reset
+    pub solved_at: Option<DateTime<Local>>,
+    pub solved_game_time: Option<Duration>,
+    pub probe: ProbeStats,
+    // Custom values set by `set_field` rules
+    pub fields: BTreeMap<String, String>,
+}
+
+impl Puzzle {
+    pub fn new(name: String, ip: String) -> Self {
+        let first = StateChange { state: PuzzleState::Registered, at: timebase::local_now(), game_time: None };
+        Self {
+            name,
+            ip,
+            registered_at: None,
+            registration: None,
+            attributes: BTreeMap::new(),
+            last_seen: timebase::local_now(),
+            reported_offline: false,
+            state: PuzzleState::Registered,
+            history: vec![first],
+            solved_at: None,
+            solved_game_time: None,
+            probe: ProbeStats::default(),
+            fields: BTreeMap::new(),
+        }
+    }
+
+    // `game_time` is the elapsed game clock, `None` if no game is running
+    pub fn set_state(&mut self, state: PuzzleState, game_time: Option<Duration>) {
+        match state {
+            PuzzleState::Solved if self.state != PuzzleState::Solved => {
+                self.solved_at = Some(timebase::local_now());
+                self.solved_game_time = game_time;
+            }
+            PuzzleState::Reset => {
+                self.solved_at = None;
+                self.solved_game_time = None;
+            }
+            _ => {}
+        }
+        self.state = state;
+        if self.history.len() >= STATE_HISTORY {
+            self.history.remove(0);
+        }
+        self.history.push(StateChange { state, at: timebase::local_now(), game_time });
+    }
+
+    // A registration attribute as shown in the views
+    pub fn attribute(&self, key: &str) -> Option<String> {
+        self.attributes.get(key).map(pyliteral::format)
+    }
+
+    // Whether a log line talks about this puzzle, by name or by IP
+    pub fn is_mentioned(&self, line: &str) -> bool {
+        contains_token(line, &self.name, |c| c.is_alphanumeric() || c == '_')
+            || (self.ip.parse::<std::net::IpAddr>().is_ok()
+                && contains_token(line, &self.ip, |c| c.is_ascii_digit() || c == '.'))
+    }
+
+    pub fn age(&self) -> Duration {
+        (timebase::local_now() - self.last_seen).to_std().unwrap_or_default()
+    }
+
+    pub fn liveness(&self, stale_after: Duration, offline_after: Duration) -> Liveness {
+        let age = self.age();
+        if age >= offline_after {
+            Liveness::Offline
+        } else if age >= stale_after {
+            Liveness::Stale
+        } else {
+            Liveness::Online
+        }
+    }
+
+    // Keeps the registration as printed and reads it into the attributes.
+    // A dict that cannot be read leaves the previous attributes.
+    pub fn read_registration(&mut self, registration: Registration) -> Result<()> {
+        let attributes = match registration {
+            Registration::Dict(dict) => {
+                let parsed = pyliteral::parse(&dict);
+                self.registration = Some(dict);
+                match parsed? {
+                    Value::Object(map) => map,
+                    _ => bail!("not a dict"),
+                }
+            }
+            Registration::Fields(fields) => {
+                self.registration = Some(Value::Object(fields.clone()).to_string());
+                fields
+            }
+        };
+        self.attributes = attributes.into_iter().collect();
+        Ok(())
+    }
+
+    // "@ 23:41" in game time, or the wall clock if no game was running
+    pub fn solved_label(&self) -> Option<String> {
+        if let Some(game_time) = self.solved_game_time {
+            Some(format!("@ {}", format_game_time(game_time)))
+        } else {
+            self.solved_at.map(|at| format!("@ {}", at.format("%H:%M:%S")))
+        }
+    }
+}
+
+// The `{...}` literal in a registration line, e.g.
+//   Puzzle registered: {'name': 'safe', 'fw': '1.2', 'ip': '10.0.0.6'}
+// Braces inside quoted strings do not count. An unclosed literal runs to the end of the line.
+pub fn registration_dict(line: &str) -> Option<&str> {
+    let start = line.find('{')?;
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in line[start..].char_indices() {
+        match (quote, c) {
+            (Some(_), _) if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '{' | '[' | '(') => depth += 1,
+            (None, '}' | ']' | ')') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&line[start..=start + i]);
+                }
+            }
+            (None, _) => {}
+        }
+    }
+    Some(&line[start..])
+}
+
+// "12s ago", "4m ago", "2h ago"
+pub fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..60 => format!("{}s ago", secs),
+        60..3600 => format!("{}m ago", secs / 60),
+        _ => format!("{}h ago", secs / 3600),
+    }
+}
+
+// Substring match that does not count hits inside a longer token,
+// so "safe" does not match "safe2" and "10.0.0.5" does not match "10.0.0.50"
+pub fn contains_token(haystack: &str, needle: &str, is_token_char: fn(char) -> bool) -> bool {
+    if needle.is_empty() {
+        return false;
+    }
+    haystack.match_indices(needle).any(|(start, _)| {
+        let before = haystack[..start].chars().next_back();
+        let after = haystack[start + needle.len()..].chars().next();
+        !before.is_some_and(is_token_char) && !after.is_some_and(is_token_char)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn puzzle() -> Puzzle {
+        Puzzle::new("safe".to_string(), "10.0.0.6".to_string())
+    }
+
+    #[test]
+    fn registration_dict_is_found() {
+        let line = "Puzzle registered: {'name': 'safe', 'ip': '10.0.0.6'} (took 3ms)";
+        assert_eq!(registration_dict(line), Some("{'name': 'safe', 'ip': '10.0.0.6'}"));
+        assert_eq!(registration_dict("Puzzle registered: safe"), None);
+    }
+
+    #[test]
+    fn registration_dict_skips_braces_in_quotes() {
+        let line = r#"Registered {'name': 'door', 'code': '}{', 'hint': "it's \"}\"", 'pins': [(1, 2)]} ok"#;
+        assert_eq!(
+            registration_dict(line),
+            Some(r#"{'name': 'door', 'code': '}{', 'hint': "it's \"}\"", 'pins': [(1, 2)]}"#)
+        );
+    }
+
+    #[test]
+    fn unclosed_registration_dict_runs_to_the_end() {
+        assert_eq!(registration_dict("Registered {'name': 'door', 'fw': '1"), Some("{'name': 'door', 'fw': '1"));
+    }
+
+    #[test]
+    fn state_history_keeps_the_newest_changes() {
+        let mut puzzle = puzzle();
+        let game_time = Duration::from_secs(754);
+        puzzle.set_state(PuzzleState::Active, None);
+        puzzle.set_state(PuzzleState::Solved, Some(game_time));
+        assert_eq!(puzzle.history.len(), 3);
+        assert!(puzzle.history[0].state == PuzzleState::Registered);
+        assert!(puzzle.history[2].state == PuzzleState::Solved);
+        assert_eq!(puzzle.history[2].game_time, Some(game_time));
+
+        for _ in 0..STATE_HISTORY {
+            puzzle.set_state(PuzzleState::Reset, None);
+        }
+        assert_eq!(puzzle.history.len(), STATE_HISTORY);
+        assert!(puzzle.history.iter().all(|c| c.state == PuzzleState::Reset));
+    }
+
+    #[test]
+    fn registration_dict_is_read() {
+        let mut puzzle = puzzle();
+        let dict = "{'name': 'safe', 'fw': '1.4.2', 'port': 5000, 'caps': ('code', 'led'), 'debug': False}";
+        puzzle.read_registration(Registration::Dict(dict.to_string())).unwrap();
+        assert_eq!(puzzle.registration.as_deref(), Some(dict));
+        assert_eq!(puzzle.attribute("fw").as_deref(), Some("1.4.2"));
+        assert_eq!(puzzle.attribute("port").as_deref(), Some("5000"));
+        assert_eq!(puzzle.attribute("caps").as_deref(), Some("[code, led]"));
+        assert_eq!(puzzle.attribute("debug").as_deref(), Some("False"));
+    }
+
+    #[test]
+    fn registration_fields_are_taken_as_they_are() {
+        let mut puzzle = puzzle();
+        let fields = serde_json::json!({"puzzle": "safe", "fw": "1.4.2", "port": 5000});
+        let Value::Object(fields) = fields else { unreachable!() };
+        puzzle.read_registration(Registration::Fields(fields)).unwrap();
+        assert_eq!(puzzle.registration.as_deref(), Some(r#"{"fw":"1.4.2","port":5000,"puzzle":"safe"}"#));
+        assert_eq!(puzzle.attribute("fw").as_deref(), Some("1.4.2"));
+        assert_eq!(puzzle.attribute("port").as_deref(), Some("5000"));
+    }
+
+    #[test]
+    fn unreadable_registration_keeps_the_attributes() {
+        let mut puzzle = puzzle();
+        puzzle.read_registration(Registration::Dict("{'fw': '1.0'}".to_string())).unwrap();
+
+        let broken = "{'name': 'safe', 'fw': <object at 0x7f>}";
+        assert!(puzzle.read_registration(Registration::Dict(broken.to_string())).is_err());
+        assert_eq!(puzzle.registration.as_deref(), Some(broken));
+        assert_eq!(puzzle.attribute("fw").as_deref(), Some("1.0"));
+
+        let error = puzzle.read_registration(Registration::Dict("['safe']".to_string())).unwrap_err();
+        assert_eq!(error.to_string(), "not a dict");
+    }
+}
@@ -0,0 +1,295 @@
+use anyhow::{bail, Result};
+use serde_json::{Map, Number, Value};
+
+// --- Python Literals ---
+//
+// The server prints dicts with Python's repr, e.g.
+//   {'name': 'safe', 'port': 5000, 'caps': ('code', 'led'), 'debug': False, 'ip': '10.0.0.6'}
+// They are read into JSON values: tuples become lists, None becomes null.
+// JSON parses as well, so both kinds of registration lines end up the same.
+
+// Deeper nesting is refused instead of overflowing the stack
+const MAX_DEPTH: usize = 32;
+
+pub fn parse(text: &str) -> Result<Value> {
+    let mut parser = Parser { chars: text.chars().collect(), pos: 0, depth: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if let Some(c) = parser.peek() {
+        bail!("Unexpected '{}' at position {}", c, parser.pos);
+    }
+    Ok(value)
+}
+
+// Python style, strings without quotes at the top level: "1.4.2", "[code, led]", "True"
+pub fn format(value: &Value) -> String {
+    match value {
+        Value::Null => "None".to_string(),
+        Value::Bool(true) => "True".to_string(),
+        Value::Bool(false) => "False".to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => format!("[{}]", items.iter().map(format).collect::<Vec<_>>().join(", ")),
+        Value::Object(map) => {
+            let entries: Vec<String> = map.iter().map(|(k, v)| format!("{}: {}", k, format(v))).collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    // Dicts and sequences currently open
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    // Consumes `c` if it comes next, after any whitespace
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        let found = self.peek() == Some(c);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        if !self.eat(c) {
+            match self.peek() {
+                Some(found) => bail!("Expected '{}' but found '{}' at position {}", c, found, self.pos),
+                None => bail!("Expected '{}' but the text ended", c),
+            }
+        }
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.nested(Self::dict),
+            Some('[') => self.nested(|p| p.sequence('[', ']')),
+            Some('(') => self.nested(|p| p.sequence('(', ')')),
+            Some('\'' | '"') => self.string(false).map(Value::String),
+            Some(c) if c.is_ascii_digit() || matches!(c, '-' | '+' | '.') => self.number(),
+            Some(c) if c.is_alphabetic() || c == '_' => self.word(),
+            Some(c) => bail!("Unexpected '{}' at position {}", c, self.pos),
+            None => bail!("Expected a value but the text ended"),
+        }
+    }
+
+    fn nested(&mut self, parse: impl FnOnce(&mut Self) -> Result<Value>) -> Result<Value> {
+        if self.depth == MAX_DEPTH {
+            bail!("Nested deeper than {} levels at position {}", MAX_DEPTH, self.pos);
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn dict(&mut self) -> Result<Value> {
+        self.expect('{')?;
+        let mut map = Map::new();
+        while !self.eat('}') {
+            // Keys other than strings (e.g. numbers) are kept as their text
+            let key = match self.value()? {
+                Value::String(s) => s,
+                other => format(&other),
+            };
+            self.expect(':')?;
+            map.insert(key, self.value()?);
+            if !self.eat(',') {
+                self.expect('}')?;
+                break;
+            }
+        }
+        Ok(Value::Object(map))
+    }
+
+    // Lists and tuples, a trailing comma is allowed
+    fn sequence(&mut self, open: char, close: char) -> Result<Value> {
+        self.expect(open)?;
+        let mut items = Vec::new();
+        while !self.eat(close) {
+            items.push(self.value()?);
+            if !self.eat(',') {
+                self.expect(close)?;
+                break;
+            }
+        }
+        Ok(Value::Array(items))
+    }
+
+    // Starts at the opening quote. Raw strings (r'...') keep their backslashes.
+    fn string(&mut self, raw: bool) -> Result<String> {
+        let quote = self.chars[self.pos];
+        self.pos += 1;
+        let mut text = String::new();
+        loop {
+            let Some(c) = self.peek() else { bail!("Unterminated string") };
+            self.pos += 1;
+            match c {
+                c if c == quote => return Ok(text),
+                '\\' if !raw => {
+                    let Some(escaped) = self.peek() else { bail!("Unterminated string") };
+                    self.pos += 1;
+                    text.push(match escaped {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        '0' => '\0',
+                        other => other,
+                    });
+                }
+                c => text.push(c),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Value> {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            let sign_after_exponent = matches!(c, '+' | '-')
+                && (self.pos == start || matches!(self.chars[self.pos - 1], 'e' | 'E'));
+            if !(c.is_ascii_alphanumeric() || matches!(c, '.' | '_') || sign_after_exponent) {
+                break;
+            }
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().filter(|&&c| c != '_').collect();
+
+        if let Ok(n) = text.parse::<i64>() {
+            return Ok(Value::Number(n.into()));
+        }
+        if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X"))
+            && let Ok(n) = i64::from_str_radix(hex, 16)
+        {
+            return Ok(Value::Number(n.into()));
+        }
+        match text.parse::<f64>().ok().and_then(Number::from_f64) {
+            Some(n) => Ok(Value::Number(n)),
+            None => bail!("Invalid number '{}'", text),
+        }
+    }
+
+    // Python and JSON constants, and string prefixes like b'...' or r"..."
+    fn word(&mut self) -> Result<Value> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.pos += 1;
+        }
+        let word: String = self.chars[start..self.pos].iter().collect();
+        match word.as_str() {
+            "True" | "true" => Ok(Value::Bool(true)),
+            "False" | "false" => Ok(Value::Bool(false)),
+            "None" | "null" => Ok(Value::Null),
+            prefix if matches!(self.peek(), Some('\'' | '"'))
+                && prefix.len() <= 2
+                && prefix.chars().all(|c| matches!(c.to_ascii_lowercase(), 'r' | 'b' | 'u')) =>
+            {
+                let raw = prefix.contains(['r', 'R']);
+                self.string(raw).map(Value::String)
+            }
+            _ => bail!("Unknown name '{}' at position {}", word, start),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn error(text: &str) -> String {
+        format!("{:#}", parse(text).unwrap_err())
+    }
+
+    #[test]
+    fn strings_and_escapes() {
+        assert_eq!(parse(r#"'it\'s'"#).unwrap(), "it's");
+        assert_eq!(parse(r#""say \"hi\"""#).unwrap(), r#"say "hi""#);
+        assert_eq!(parse(r"'a\tb\nc\\'").unwrap(), "a\tb\nc\\");
+        assert_eq!(parse(r#"'{"x": 1}'"#).unwrap(), r#"{"x": 1}"#);
+        assert_eq!(parse(r"r'C:\temp\new'").unwrap(), r"C:\temp\new");
+        assert_eq!(parse("b'raw'").unwrap(), "raw");
+        assert_eq!(parse("u'text'").unwrap(), "text");
+    }
+
+    #[test]
+    fn constants_and_numbers() {
+        assert_eq!(parse("True").unwrap(), true);
+        assert_eq!(parse("False").unwrap(), false);
+        assert_eq!(parse("None").unwrap(), Value::Null);
+        assert_eq!(parse("null").unwrap(), Value::Null);
+        assert_eq!(parse("-42").unwrap(), -42);
+        assert_eq!(parse("0x1F").unwrap(), 31);
+        assert_eq!(parse("1_000").unwrap(), 1000);
+        assert_eq!(parse("2.5e-3").unwrap(), 0.0025);
+        assert!(error("nan").contains("Unknown name 'nan'"));
+        assert!(error("1.2.3").contains("Invalid number '1.2.3'"));
+    }
+
+    #[test]
+    fn tuples_become_lists() {
+        assert_eq!(parse("('code', 'led')").unwrap(), json!(["code", "led"]));
+        assert_eq!(parse("(1,)").unwrap(), json!([1]));
+        assert_eq!(parse("()").unwrap(), json!([]));
+    }
+
+    #[test]
+    fn nested_dicts() {
+        let text = "{'name': 'safe', 'net': {'ip': '10.0.0.6', 'ports': [5000, 5001]}, 'debug': False, 1: None}";
+        let expected = json!({
+            "name": "safe",
+            "net": {"ip": "10.0.0.6", "ports": [5000, 5001]},
+            "debug": false,
+            "1": null,
+        });
+        assert_eq!(parse(text).unwrap(), expected);
+        // JSON reads the same
+        assert_eq!(parse(&expected.to_string()).unwrap(), expected);
+    }
+
+    #[test]
+    fn trailing_commas() {
+        assert_eq!(parse("{'a': 1, 'b': [2, 3,],}").unwrap(), json!({"a": 1, "b": [2, 3]}));
+        assert!(parse("[1,,2]").is_err());
+    }
+
+    #[test]
+    fn truncated_literals() {
+        assert!(error("{'name': 'safe', 'fw'").contains("Expected ':' but the text ended"));
+        assert!(error("{'name': 'sa").contains("Unterminated string"));
+        assert!(error("[1, 2").contains("Expected ']' but the text ended"));
+        assert!(error("{'a': 1} trailing").contains("Unexpected 't'"));
+        assert!(error("").contains("Expected a value"));
+    }
+
+    #[test]
+    fn deep_nesting_is_refused() {
+        let ok = format!("{}{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH));
+        assert!(parse(&ok).is_ok());
+        let deep = format!("{}{}", "[".repeat(MAX_DEPTH + 1), "]".repeat(MAX_DEPTH + 1));
+        assert!(error(&deep).contains("Nested deeper than"));
+        // Far too deep for the stack if it were followed
+        assert!(parse(&"{'a': ".repeat(100_000)).is_err());
+    }
+
+    #[test]
+    fn format_is_python_style() {
+        let value = json!({"caps": ["code", "led"], "debug": false, "fw": "1.4.2", "ip": null});
+        assert_eq!(format(&value), "{caps: [code, led], debug: False, fw: 1.4.2, ip: None}");
+    }
+}
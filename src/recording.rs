@@ -0,0 +1,309 @@
+use crate::{
+    game::GameResult,
+    logs::{LogLevel, LogSource},
+    metrics::Sample,
+    timebase, App,
+};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, TimeDelta};
+use crossterm::event::KeyCode;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+const SPEEDS: [u32; 3] = [1, 2, 10];
+
+// --- Recorded Events ---
+//
+// One JSON object per line, e.g.
+//   {"at":"2026-10-16T14:49:18.123+02:00","event":"line","stderr":false,"text":"..."}
+// Everything derived from these (puzzles, clients, alerts, ...) is rebuilt on replay.
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockAction {
+    Start,
+    Pause,
+    Resume,
+    Reset,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    // A line from the server, as passed to `process_log`
+    Line { stderr: bool, text: String },
+    Clock { action: ClockAction },
+    Hint { puzzle: String, text: String },
+    GameStart { team: String, players: u32 },
+    GameEnd { result: GameResult },
+    // System and server load, once per `[metrics] sample_secs`
+    Sample(Sample),
+    // Supervisor and operator entries in the log pane
+    Log { source: LogSource, level: LogLevel, message: String },
+}
+
+#[derive(Serialize, Deserialize)]
+struct Record {
+    at: DateTime<Local>,
+    #[serde(flatten)]
+    event: Event,
+}
+
+// --- Recorder ---
+
+pub struct Recorder {
+    file: File,
+    path: PathBuf,
+}
+
+impl Recorder {
+    // "recordings/session-20261016-144918.jsonl"
+    pub fn create(dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create recording directory {}", dir.display()))?;
+        let path = dir.join(format!("session-{}.jsonl", Local::now().format("%Y%m%d-%H%M%S")));
+        let file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&path)
+            .with_context(|| format!("Failed to create recording {}", path.display()))?;
+        Ok(Self { file, path })
+    }
+
+    pub fn record(&mut self, event: Event) -> Result<()> {
+        let mut line = serde_json::to_vec(&Record { at: timebase::local_now(), event })?;
+        line.push(b'\n');
+        self.file
+            .write_all(&line)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+// --- Replay ---
+
+// Feeds a recording back into the app. Time is driven through `timebase`, so
+// pausing, speeding up and seeking also move the game clock and all ages.
+pub struct Replay {
+    path: PathBuf,
+    // Events with their offset from the first one
+    events: Vec<(Duration, Event)>,
+    start: DateTime<Local>,
+    // Virtual instant of the first event
+    anchor: Instant,
+    position: Duration,
+    next: usize,
+    speed: usize,
+    paused: bool,
+    last_tick: Instant,
+}
+
+impl Replay {
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path).with_context(|| format!("Failed to open recording {}", path.display()))?;
+        let mut records = Vec::new();
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: Record = serde_json::from_str(&line)
+                .with_context(|| format!("Invalid recording {}, line {}", path.display(), i + 1))?;
+            records.push(record);
+        }
+        let Some(first) = records.first() else {
+            bail!("Recording {} is empty", path.display());
+        };
+
+        let start = first.at;
+        let events = records
+            .into_iter()
+            .map(|r| ((r.at - start).to_std().unwrap_or_default(), r.event))
+            .collect();
+        let now = Instant::now();
+        Ok(Self {
+            path: path.to_path_buf(),
+            events,
+            start,
+            anchor: now,
+            position: Duration::ZERO,
+            next: 0,
+            speed: 0,
+            paused: false,
+            last_tick: now,
+        })
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.events.last().map_or(Duration::ZERO, |(offset, _)| *offset)
+    }
+
+    // Called every frame, advances by the real time passed times the speed
+    pub fn tick(&mut self, app: &mut App) {
+        let now = Instant::now();
+        if !self.paused {
+            let step = now.duration_since(self.last_tick) * SPEEDS[self.speed];
+            self.position = (self.position + step).min(self.duration());
+        }
+        self.last_tick = now;
+        self.feed(app);
+        app.replay_status = Some(self.label());
+    }
+
+    // Space pauses, ←/→ seek 10s, [/] seek a minute, +/- change the speed.
+    // Server, clock, command and game keys are swallowed, there is no server to talk to.
+    pub fn handle_key(&mut self, code: KeyCode, app: &mut App) -> bool {
+        match code {
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Left => self.seek(app, -10),
+            KeyCode::Right => self.seek(app, 10),
+            KeyCode::Char('[') => self.seek(app, -60),
+            KeyCode::Char(']') => self.seek(app, 60),
+            KeyCode::Char('+') => self.speed = (self.speed + 1).min(SPEEDS.len() - 1),
+            KeyCode::Char('-') => self.speed = self.speed.saturating_sub(1),
+            KeyCode::Char('S' | 'X' | 'R' | 't' | 'T' | ':' | 'g' | 'G') => {}
+            _ => return false,
+        }
+        app.replay_status = Some(self.label());
+        true
+    }
+
+    // Going back starts over from an empty state and replays up to the target at once
+    fn seek(&mut self, app: &mut App, secs: i64) {
+        let target = if secs < 0 {
+            self.position.saturating_sub(Duration::from_secs(secs.unsigned_abs()))
+        } else {
+            (self.position + Duration::from_secs(secs as u64)).min(self.duration())
+        };
+        if target < self.position {
+            app.clear_state();
+            self.next = 0;
+        }
+        self.position = target;
+        self.feed(app);
+    }
+
+    fn feed(&mut self, app: &mut App) {
+        while let Some((offset, event)) = self.events.get(self.next)
+            && *offset <= self.position
+        {
+            self.set_time(*offset);
+            app.replay_event(event.clone());
+            self.next += 1;
+        }
+        self.set_time(self.position);
+    }
+
+    fn set_time(&self, offset: Duration) {
+        let local = self.start + TimeDelta::from_std(offset).unwrap_or_default();
+        timebase::set(self.anchor + offset, local);
+    }
+
+    // "session-20261016-144918.jsonl ▶ 2x 03:12 / 45:00"
+    fn label(&self) -> String {
+        let mmss = |d: Duration| format!("{:02}:{:02}", d.as_secs() / 60, d.as_secs() % 60);
+        let state = if self.position >= self.duration() {
+            "■ END".to_string()
+        } else if self.paused {
+            "⏸ PAUSED".to_string()
+        } else {
+            format!("▶ {}x", SPEEDS[self.speed])
+        };
+        let name = self.path.file_name().unwrap_or(self.path.as_os_str()).to_string_lossy();
+        format!("{} {} {} / {}", name, state, mmss(self.position), mmss(self.duration()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn app() -> App {
+        let mut config = Config::default();
+        config.logs.save = false;
+        config.recording.enabled = false;
+        App::new(&config).unwrap()
+    }
+
+    fn lines(app: &App) -> Vec<String> {
+        app.logs.iter_from(0).map(|(_, e)| e.text.clone()).collect()
+    }
+
+    fn line(text: &str) -> Event {
+        Event::Line { stderr: false, text: text.to_string() }
+    }
+
+    // Writes a recording with events at the given seconds
+    fn record(name: &str, events: Vec<(u64, Event)>) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("servertui-test-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut recorder = Recorder::create(&dir).unwrap();
+        let (instant, local) = (Instant::now(), Local::now());
+        for (secs, event) in events {
+            timebase::set(instant + Duration::from_secs(secs), local + TimeDelta::seconds(secs as i64));
+            recorder.record(event).unwrap();
+        }
+        recorder.path
+    }
+
+    #[test]
+    fn recording_replays_with_seek_and_speed() {
+        let _time = timebase::test_lock();
+        let path = record(
+            "replay",
+            vec![
+                (0, line("first")),
+                (0, Event::Clock { action: ClockAction::Start }),
+                (30, line("second")),
+                (90, Event::Log { source: LogSource::Operator, level: LogLevel::Info, message: "third".to_string() }),
+            ],
+        );
+        let mut replay = Replay::load(&path).unwrap();
+        assert_eq!(replay.duration(), Duration::from_secs(90));
+
+        // Paused, so the position only moves by seeking
+        let mut app = app();
+        assert!(replay.handle_key(KeyCode::Char(' '), &mut app));
+        replay.tick(&mut app);
+        assert_eq!(lines(&app), ["first"]);
+        assert!(app.clock.is_running());
+        assert_eq!(app.clock.elapsed(), Duration::ZERO);
+
+        for _ in 0..3 {
+            replay.handle_key(KeyCode::Right, &mut app);
+        }
+        assert_eq!(lines(&app), ["first", "second"]);
+        assert_eq!(app.clock.elapsed(), Duration::from_secs(30));
+
+        replay.handle_key(KeyCode::Char(']'), &mut app);
+        assert_eq!(lines(&app), ["first", "second", "third"]);
+        assert!(app.replay_status.as_deref().unwrap().ends_with("■ END 01:30 / 01:30"));
+
+        // Going back rebuilds the state instead of keeping the later lines
+        replay.handle_key(KeyCode::Char('['), &mut app);
+        assert_eq!(lines(&app), ["first", "second"]);
+        assert_eq!(app.clock.elapsed(), Duration::from_secs(30));
+        assert!(app.replay_status.as_deref().unwrap().ends_with("⏸ PAUSED 00:30 / 01:30"));
+
+        replay.handle_key(KeyCode::Char(' '), &mut app);
+        let speeds: Vec<String> = ['+', '+', '+', '-', '-', '-']
+            .into_iter()
+            .map(|key| {
+                replay.handle_key(KeyCode::Char(key), &mut app);
+                app.replay_status.as_deref().unwrap().split(' ').nth(2).unwrap().to_string()
+            })
+            .collect();
+        assert_eq!(speeds, ["2x", "10x", "10x", "2x", "1x", "1x"]);
+
+        // Server and clock keys do nothing during a replay
+        assert!(replay.handle_key(KeyCode::Char('T'), &mut app));
+        assert!(app.clock.is_running());
+        assert!(!replay.handle_key(KeyCode::Char('q'), &mut app));
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,357 @@
+use crate::{
+    logs::{LogLevel, LogSource},
+    puzzle::{registration_dict, PuzzleState, Registration},
+    App,
+};
+use anyhow::{bail, Context, Result};
+use regex::{Captures, Regex};
+use serde::Deserialize;
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+// Built-in rules, used when no rule file is configured
+const DEFAULT_RULES: &str = include_str!("../rules.default.toml");
+
+// --- Rule Definitions ---
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClientKind {
+    #[default]
+    Http,
+    Udp,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Action {
+    RegisterPuzzle,
+    UpdatePuzzleIp,
+    MarkActive,
+    MarkSolved,
+    MarkReset,
+    MarkError,
+    RegisterClient {
+        #[serde(default)]
+        kind: ClientKind,
+    },
+    MarkServerReady,
+    RaiseAlert {
+        message: Option<String>,
+    },
+    SetField {
+        field: String,
+    },
+}
+
+impl Action {
+    // Capture groups the action cannot work without
+    fn required_groups(&self) -> &'static [&'static str] {
+        match self {
+            Action::RegisterPuzzle
+            | Action::MarkActive
+            | Action::MarkSolved
+            | Action::MarkReset
+            | Action::MarkError => &["name"],
+            Action::UpdatePuzzleIp => &["name", "ip"],
+            Action::RegisterClient { .. } => &["ip"],
+            Action::SetField { .. } => &["value"],
+            Action::MarkServerReady | Action::RaiseAlert { .. } => &[],
+        }
+    }
+
+    pub fn puzzle_state(&self) -> Option<PuzzleState> {
+        match self {
+            Action::MarkActive => Some(PuzzleState::Active),
+            Action::MarkSolved => Some(PuzzleState::Solved),
+            Action::MarkReset => Some(PuzzleState::Reset),
+            Action::MarkError => Some(PuzzleState::Error),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RuleDef {
+    name: Option<String>,
+    pattern: String,
+    #[serde(default)]
+    stop: bool,
+    #[serde(flatten)]
+    action: Action,
+}
+
+#[derive(Deserialize)]
+struct RuleFile {
+    #[serde(default, rename = "rule")]
+    rules: Vec<RuleDef>,
+}
+
+// --- Compiled Rules ---
+
+struct Rule {
+    regex: Regex,
+    action: Action,
+    stop: bool,
+}
+
+// What a matching rule extracted from a line
+pub struct Hit {
+    pub action: Action,
+    pub name: Option<String>,
+    pub ip: Option<String>,
+    pub path: Option<String>,
+    pub value: Option<String>,
+    pub message: String,
+    // Only for `register_puzzle`
+    pub registration: Option<Registration>,
+}
+
+pub struct Rules {
+    rules: Vec<Rule>,
+}
+
+impl Rules {
+    // Loads the configured rule file, or the built-in rules without one
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        match path {
+            Some(path) => {
+                let text = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read rule file {}", path.display()))?;
+                Self::parse(&text).with_context(|| format!("Invalid rule file {}", path.display()))
+            }
+            None => Self::parse(DEFAULT_RULES).context("Invalid built-in rules"),
+        }
+    }
+
+    fn parse(text: &str) -> Result<Self> {
+        let file: RuleFile = toml::from_str(text)?;
+        let mut rules = Vec::new();
+
+        for (i, def) in file.rules.into_iter().enumerate() {
+            let label = def.name.unwrap_or_else(|| format!("#{}", i + 1));
+            let regex = Regex::new(&def.pattern)
+                .with_context(|| format!("Rule {}: invalid pattern", label))?;
+            for group in def.action.required_groups() {
+                if !regex.capture_names().any(|n| n == Some(group)) {
+                    bail!("Rule {}: pattern needs a (?P<{}>...) group", label, group);
+                }
+            }
+            rules.push(Rule { regex, action: def.action, stop: def.stop });
+        }
+
+        Ok(Self { rules })
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn evaluate(&self, line: &str) -> Vec<Hit> {
+        let mut hits = Vec::new();
+        for rule in &self.rules {
+            let Some(caps) = rule.regex.captures(line) else {
+                continue;
+            };
+
+            let group = |name: &str| caps.name(name).map(|m| m.as_str().to_string());
+            let message = match &rule.action {
+                Action::RaiseAlert { message: Some(template) } => expand(&caps, template),
+                _ => line.to_string(),
+            };
+            // The dict is read into attributes when the hit is applied. It is looked for
+            // from where the rule matched, braces in a prefix like "[{worker-1}]" are not it.
+            let registration = match rule.action {
+                Action::RegisterPuzzle => {
+                    let matched = &line[caps.get(0).map_or(0, |m| m.start())..];
+                    registration_dict(matched).map(|d| Registration::Dict(d.to_string()))
+                }
+                _ => None,
+            };
+            hits.push(Hit {
+                action: rule.action.clone(),
+                name: group("name"),
+                ip: group("ip"),
+                path: group("path"),
+                value: group("value"),
+                message,
+                registration,
+            });
+
+            if rule.stop {
+                break;
+            }
+        }
+        hits
+    }
+}
+
+fn expand(caps: &Captures, template: &str) -> String {
+    let mut out = String::new();
+    caps.expand(template, &mut out);
+    out
+}
+
+// --- Hot Reload ---
+
+// Polls the rule file and swaps the rules in place when it changes. A broken
+// file is reported in the log and the previous rules stay active.
+pub fn watch(path: PathBuf, app: Arc<Mutex<App>>) {
+    thread::spawn(move || {
+        let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        let mut last: Option<SystemTime> = modified(&path);
+
+        loop {
+            thread::sleep(Duration::from_secs(1));
+            let current = modified(&path);
+            if current == last {
+                continue;
+            }
+            last = current;
+
+            let result = Rules::load(Some(&path));
+            let mut app = app.lock().unwrap();
+            match result {
+                Ok(rules) => {
+                    let count = rules.len();
+                    app.rules = Arc::new(rules);
+                    app.log_marked(
+                        LogSource::Rules,
+                        LogLevel::Info,
+                        format!("Reloaded {} rules from {}", count, path.display()),
+                    );
+                }
+                Err(e) => {
+                    app.log_marked(
+                        LogSource::Rules,
+                        LogLevel::Error,
+                        format!("Reload failed, keeping previous rules: {:#}", e),
+                    );
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn single(rules: &Rules, line: &str) -> Hit {
+        let mut hits = rules.evaluate(line);
+        assert_eq!(hits.len(), 1, "expected one hit for {:?}", line);
+        hits.remove(0)
+    }
+
+    #[test]
+    fn default_rules_load() {
+        let rules = Rules::load(None).unwrap();
+        assert!(rules.len() > 0);
+    }
+
+    #[test]
+    fn missing_group_is_rejected() {
+        let text = r#"
+            [[rule]]
+            name = "moved"
+            pattern = 'Puzzle (?P<name>\w+) moved'
+            action = "update_puzzle_ip"
+        "#;
+        let error = format!("{:#}", Rules::parse(text).err().unwrap());
+        assert!(error.contains("Rule moved: pattern needs a (?P<ip>...) group"), "{}", error);
+    }
+
+    #[test]
+    fn invalid_pattern_is_rejected() {
+        let text = r#"
+            [[rule]]
+            pattern = 'Puzzle (?P<name>\w+'
+            action = "mark_solved"
+        "#;
+        let error = format!("{:#}", Rules::parse(text).err().unwrap());
+        assert!(error.contains("Rule #1: invalid pattern"), "{}", error);
+    }
+
+    #[test]
+    fn unknown_action_is_rejected() {
+        let text = r#"
+            [[rule]]
+            pattern = 'x'
+            action = "explode"
+        "#;
+        assert!(Rules::parse(text).is_err());
+    }
+
+    #[test]
+    fn registration_dict_is_found() {
+        let rules = Rules::load(None).unwrap();
+        let hit = single(&rules, "[{worker-1}] Puzzle registered: {'name': 'laser', 'ip': '10.0.0.7'} (took 3ms)");
+        assert_eq!(hit.name.as_deref(), Some("laser"));
+        match hit.registration {
+            Some(Registration::Dict(dict)) => assert_eq!(dict, "{'name': 'laser', 'ip': '10.0.0.7'}"),
+            _ => panic!("expected the registration dict"),
+        }
+
+        // Other registration lines carry no dict
+        let hit = single(&rules, "Registering new puzzle safe");
+        assert!(matches!(hit.action, Action::RegisterPuzzle));
+        assert!(hit.registration.is_none());
+    }
+
+    #[test]
+    fn captures_map_to_the_action() {
+        let rules = Rules::load(None).unwrap();
+
+        let hit = single(&rules, "Puzzle registered: {'name': 'laser', 'fw': '2.0', 'ip': '10.0.0.7', 'port': 5001}");
+        assert!(matches!(hit.action, Action::RegisterPuzzle));
+        assert_eq!(hit.name.as_deref(), Some("laser"));
+        assert_eq!(hit.ip.as_deref(), Some("10.0.0.7"));
+
+        match hit.registration {
+            Some(Registration::Dict(dict)) => assert!(dict.starts_with("{'name': 'laser'") && dict.ends_with("5001}")),
+            _ => panic!("expected the registration dict"),
+        }
+
+        let hit = single(&rules, "Puzzle 'safe' was solved");
+        assert!(hit.registration.is_none());
+        assert!(matches!(hit.action, Action::MarkSolved));
+        assert_eq!(hit.name.as_deref(), Some("safe"));
+        assert!(matches!(hit.action.puzzle_state(), Some(PuzzleState::Solved)));
+
+        let hit = single(&rules, r#"10.0.0.20 - - [16/Oct/2026 15:19:08] "GET /state HTTP/1.1" 200 -"#);
+        assert!(matches!(hit.action, Action::RegisterClient { kind: ClientKind::Http }));
+        assert_eq!(hit.ip.as_deref(), Some("10.0.0.20"));
+        assert_eq!(hit.path.as_deref(), Some("/state"));
+
+        let hit = single(&rules, "Received message from ('10.0.0.21', 40000): ping");
+        assert!(matches!(hit.action, Action::RegisterClient { kind: ClientKind::Udp }));
+        assert_eq!(hit.ip.as_deref(), Some("10.0.0.21"));
+
+        assert!(rules.evaluate("Nothing to see here").is_empty());
+    }
+
+    #[test]
+    fn alert_message_and_stop() {
+        let text = r#"
+            [[rule]]
+            pattern = 'Temperature (?P<value>\d+)'
+            action = "raise_alert"
+            message = "Too hot: $value"
+            stop = true
+
+            [[rule]]
+            pattern = 'Temperature (?P<value>\d+)'
+            action = "set_field"
+            field = "temp"
+        "#;
+        let rules = Rules::parse(text).unwrap();
+        let hit = single(&rules, "Temperature 85");
+        assert!(matches!(hit.action, Action::RaiseAlert { .. }));
+        assert_eq!(hit.message, "Too hot: 85");
+        assert_eq!(hit.value.as_deref(), Some("85"));
+    }
+}
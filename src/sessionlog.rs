@@ -0,0 +1,193 @@
+use crate::{config::LogsConfig, logs::LogSource};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+const FILE_PREFIX: &str = "servertui-";
+const FILE_SUFFIX: &str = ".log";
+
+// --- Session Log File ---
+//
+// Every line shown in the log pane is also appended to a file in `[logs] dir`,
+// one file per run. A file that grows too large or too old is closed and the
+// run continues in a new one; only the newest `keep_files` are kept.
+
+pub struct SessionLog {
+    dir: PathBuf,
+    max_bytes: u64,
+    max_age: Duration,
+    keep_files: usize,
+    file: File,
+    path: PathBuf,
+    opened: Instant,
+    written: u64,
+}
+
+impl SessionLog {
+    pub fn open(config: &LogsConfig) -> Result<Self> {
+        std::fs::create_dir_all(&config.dir)
+            .with_context(|| format!("Failed to create log directory {}", config.dir.display()))?;
+        let (file, path) = create_file(&config.dir)?;
+        let log = Self {
+            dir: config.dir.clone(),
+            max_bytes: config.max_file_mb * 1024 * 1024,
+            max_age: Duration::from_secs(config.max_file_hours * 3600),
+            keep_files: config.keep_files,
+            file,
+            path,
+            opened: Instant::now(),
+            written: 0,
+        };
+        log.remove_old_files();
+        Ok(log)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // "2026-10-16 14:47:52 [SUPERVISOR] Started ..."
+    pub fn write(&mut self, timestamp: DateTime<Local>, source: LogSource, text: &str) -> Result<()> {
+        if self.written >= self.max_bytes || self.opened.elapsed() >= self.max_age {
+            self.rotate()?;
+        }
+
+        let line = match source.tag() {
+            Some(tag) => format!("{} [{}] {}\n", timestamp.format("%Y-%m-%d %H:%M:%S"), tag, text),
+            None => format!("{} {}\n", timestamp.format("%Y-%m-%d %H:%M:%S"), text),
+        };
+        self.file
+            .write_all(line.as_bytes())
+            .with_context(|| format!("Failed to write {}", self.path.display()))?;
+        self.written += line.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        let (file, path) = create_file(&self.dir)?;
+        let previous = std::mem::replace(&mut self.path, path);
+        self.file = file;
+        self.opened = Instant::now();
+        self.written = 0;
+
+        let note = format!("Continued from {}\n", previous.display());
+        self.file.write_all(note.as_bytes())?;
+        self.remove_old_files();
+        Ok(())
+    }
+
+    // Removes the oldest session files beyond `keep_files`, 0 keeps everything
+    fn remove_old_files(&self) {
+        if self.keep_files == 0 {
+            return;
+        }
+        let Ok(dir) = std::fs::read_dir(&self.dir) else { return };
+        let mut files: Vec<(SystemTime, PathBuf)> = dir
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                e.file_name()
+                    .to_str()
+                    .is_some_and(|n| n.starts_with(FILE_PREFIX) && n.ends_with(FILE_SUFFIX))
+            })
+            .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+            .collect();
+        files.sort();
+
+        let excess = files.len().saturating_sub(self.keep_files);
+        for (_, path) in files.into_iter().take(excess).filter(|(_, p)| *p != self.path) {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+// "servertui-20261016-144752.log", with a counter if that name is taken
+fn create_file(dir: &Path) -> Result<(File, PathBuf)> {
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    let mut n = 0;
+    loop {
+        let name = match n {
+            0 => format!("{}{}{}", FILE_PREFIX, stamp, FILE_SUFFIX),
+            n => format!("{}{}-{}{}", FILE_PREFIX, stamp, n, FILE_SUFFIX),
+        };
+        let path = dir.join(name);
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((file, path)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => n += 1,
+            Err(e) => return Err(e).with_context(|| format!("Failed to create log file {}", path.display())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open(name: &str, keep_files: usize) -> SessionLog {
+        let dir = std::env::temp_dir().join(format!("servertui-test-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        let config = LogsConfig { dir, keep_files, ..LogsConfig::default() };
+        SessionLog::open(&config).unwrap()
+    }
+
+    fn session_files(dir: &Path) -> usize {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_str().is_some_and(|n| n.starts_with(FILE_PREFIX)))
+            .count()
+    }
+
+    #[test]
+    fn large_file_is_rotated() {
+        let mut log = open("size", 0);
+        log.max_bytes = 60;
+        log.write(Local::now(), LogSource::Stdout, "first line of the old file").unwrap();
+        let first = log.path().to_path_buf();
+        log.write(Local::now(), LogSource::Stderr, "x").unwrap();
+        assert_eq!(log.path(), first);
+        log.write(Local::now(), LogSource::Stdout, "first line of the new file").unwrap();
+        assert_ne!(log.path(), first);
+
+        let old = std::fs::read_to_string(&first).unwrap();
+        assert!(old.ends_with(" [STDERR] x\n"));
+        let new = std::fs::read_to_string(log.path()).unwrap();
+        assert_eq!(new.lines().next().unwrap(), format!("Continued from {}", first.display()));
+        assert!(new.ends_with(" first line of the new file\n"));
+        std::fs::remove_dir_all(&log.dir).unwrap();
+    }
+
+    #[test]
+    fn old_file_is_rotated() {
+        let mut log = open("age", 0);
+        let first = log.path().to_path_buf();
+        log.write(Local::now(), LogSource::Stdout, "still young").unwrap();
+        assert_eq!(log.path(), first);
+        log.max_age = Duration::ZERO;
+        log.write(Local::now(), LogSource::Stdout, "too old").unwrap();
+        assert_ne!(log.path(), first);
+        assert_eq!(session_files(&log.dir), 2);
+        std::fs::remove_dir_all(&log.dir).unwrap();
+    }
+
+    #[test]
+    fn only_old_session_files_are_removed() {
+        let mut log = open("keep", 2);
+        std::fs::write(log.dir.join("server.log"), "").unwrap();
+        std::fs::write(log.dir.join("servertui-notes.txt"), "").unwrap();
+        for _ in 0..4 {
+            log.rotate().unwrap();
+        }
+
+        assert!(log.path().exists());
+        assert!(log.dir.join("server.log").exists());
+        assert!(log.dir.join("servertui-notes.txt").exists());
+        // The notes file has the prefix but is not a session log
+        assert_eq!(session_files(&log.dir), 3);
+        std::fs::remove_dir_all(&log.dir).unwrap();
+    }
+}
@@ -0,0 +1,280 @@
+use crate::game::{GameRecord, GameResult};
+use chrono::{Datelike, Timelike, Weekday};
+use std::{collections::BTreeMap, time::Duration};
+
+const LEADERBOARD_SIZE: usize = 10;
+// Games are grouped by their start hour into slots of this many hours
+const SLOT_HOURS: u32 = 3;
+const WEEKDAYS: [Weekday; 7] =
+    [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri, Weekday::Sat, Weekday::Sun];
+
+// --- Statistics ---
+//
+// Computed from the game history when the statistics view is opened.
+
+pub struct Stats {
+    pub games: usize,
+    pub escaped: usize,
+    pub average_escape: Option<Duration>,
+    // Escaped games, fastest first
+    pub leaderboard: Vec<BestTime>,
+    // Sorted by name
+    pub puzzles: Vec<PuzzleStats>,
+    pub most_blocking: Option<String>,
+    pub weekdays: Vec<SuccessRate>,
+    pub slots: Vec<SuccessRate>,
+}
+
+pub struct BestTime {
+    pub team: String,
+    pub players: u32,
+    pub date: String,
+    pub total: Duration,
+    pub hints: usize,
+}
+
+pub struct PuzzleStats {
+    pub name: String,
+    // Games the puzzle was part of, solved or not
+    pub played: usize,
+    pub solved: usize,
+    // Game time at which it was solved
+    pub average_solved_at: Option<Duration>,
+    // Time since the previous solve (or the start), i.e. how long teams worked on it
+    pub average_taken: Option<Duration>,
+    // Failed games that ended with this puzzle unsolved
+    pub blocked: usize,
+    pub hints: usize,
+}
+
+pub struct SuccessRate {
+    pub label: String,
+    pub games: usize,
+    pub escaped: usize,
+}
+
+impl SuccessRate {
+    pub fn percent(&self) -> u64 {
+        (self.escaped * 100).checked_div(self.games).unwrap_or(0) as u64
+    }
+}
+
+#[derive(Default)]
+struct PuzzleTotals {
+    played: usize,
+    solved_at: Vec<u64>,
+    taken: Vec<u64>,
+    blocked: usize,
+    hints: usize,
+}
+
+impl Stats {
+    pub fn compute(games: &[GameRecord]) -> Self {
+        let escaped: Vec<&GameRecord> = games.iter().filter(|g| g.result == GameResult::Escaped).collect();
+
+        let mut leaderboard: Vec<BestTime> = escaped
+            .iter()
+            .map(|g| BestTime {
+                team: g.team.clone(),
+                players: g.players,
+                date: g.started.format("%Y-%m-%d").to_string(),
+                total: Duration::from_secs(g.total_secs),
+                hints: g.hints.len(),
+            })
+            .collect();
+        leaderboard.sort_by_key(|b| b.total);
+        leaderboard.truncate(LEADERBOARD_SIZE);
+
+        let mut totals: BTreeMap<String, PuzzleTotals> = BTreeMap::new();
+        for game in games {
+            let mut solved: Vec<_> = game.solved.iter().collect();
+            solved.sort_by_key(|s| s.game_secs);
+            let mut previous = 0;
+            for s in solved {
+                let entry = totals.entry(s.puzzle.clone()).or_default();
+                entry.played += 1;
+                entry.solved_at.push(s.game_secs);
+                entry.taken.push(s.game_secs.saturating_sub(previous));
+                previous = s.game_secs;
+            }
+            for name in &game.unsolved {
+                let entry = totals.entry(name.clone()).or_default();
+                entry.played += 1;
+                if game.result == GameResult::Failed {
+                    entry.blocked += 1;
+                }
+            }
+            for hint in &game.hints {
+                totals.entry(hint.puzzle.clone()).or_default().hints += 1;
+            }
+        }
+        let puzzles: Vec<PuzzleStats> = totals
+            .into_iter()
+            .map(|(name, t)| PuzzleStats {
+                name,
+                played: t.played,
+                solved: t.solved_at.len(),
+                average_solved_at: average(&t.solved_at),
+                average_taken: average(&t.taken),
+                blocked: t.blocked,
+                hints: t.hints,
+            })
+            .collect();
+
+        // Left unsolved in the most failed games, ties go to the one taking longest
+        let most_blocking = puzzles
+            .iter()
+            .filter(|p| p.blocked > 0 || p.average_taken.is_some())
+            .max_by_key(|p| (p.blocked, p.average_taken))
+            .map(|p| p.name.clone());
+
+        let weekdays = WEEKDAYS
+            .iter()
+            .map(|day| success_rate(day.to_string(), games.iter().filter(|g| g.started.weekday() == *day)))
+            .filter(|r| r.games > 0)
+            .collect();
+        let slots = (0..24 / SLOT_HOURS)
+            .map(|slot| {
+                let from = slot * SLOT_HOURS;
+                let label = format!("{:02}-{:02}h", from, from + SLOT_HOURS);
+                success_rate(label, games.iter().filter(|g| g.started.hour() / SLOT_HOURS == slot))
+            })
+            .filter(|r| r.games > 0)
+            .collect();
+
+        let escape_times: Vec<u64> = escaped.iter().map(|g| g.total_secs).collect();
+        Self {
+            games: games.len(),
+            escaped: escaped.len(),
+            average_escape: average(&escape_times),
+            leaderboard,
+            puzzles,
+            most_blocking,
+            weekdays,
+            slots,
+        }
+    }
+}
+
+fn success_rate<'a>(label: String, games: impl Iterator<Item = &'a GameRecord>) -> SuccessRate {
+    let (mut total, mut escaped) = (0, 0);
+    for game in games {
+        total += 1;
+        if game.result == GameResult::Escaped {
+            escaped += 1;
+        }
+    }
+    SuccessRate { label, games: total, escaped }
+}
+
+fn average(secs: &[u64]) -> Option<Duration> {
+    (!secs.is_empty()).then(|| Duration::from_secs(secs.iter().sum::<u64>() / secs.len() as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Local, TimeZone};
+
+    // 2026-10-12 is a Monday
+    fn record(day: u32, hour: u32, result: &str, total_secs: u64, solved: &str, unsolved: Option<&str>) -> GameRecord {
+        let started = Local.with_ymd_and_hms(2026, 10, day, hour, 15, 0).unwrap();
+        let ended = started + chrono::Duration::seconds(total_secs as i64);
+        let mut json = format!(
+            r#"{{"team":"Team {day}-{hour}","players":4,"started":"{}","ended":"{}","result":"{result}",
+                "total_secs":{total_secs},"solved":[{solved}],"hints":[{{"puzzle":"laser","text":"Mirrors","game_secs":600}}]"#,
+            started.to_rfc3339(),
+            ended.to_rfc3339(),
+        );
+        // Records written before the statistics have no `unsolved` list
+        if let Some(unsolved) = unsolved {
+            json.push_str(&format!(r#","unsolved":[{}]"#, unsolved));
+        }
+        json.push('}');
+        serde_json::from_str(&json).unwrap()
+    }
+
+    fn solved(puzzle: &str, secs: u64) -> String {
+        format!(r#"{{"puzzle":"{}","game_secs":{}}}"#, puzzle, secs)
+    }
+
+    #[test]
+    fn empty_history() {
+        let stats = Stats::compute(&[]);
+        assert_eq!((stats.games, stats.escaped), (0, 0));
+        assert!(stats.average_escape.is_none());
+        assert!(stats.leaderboard.is_empty());
+        assert!(stats.puzzles.is_empty());
+        assert!(stats.most_blocking.is_none());
+        assert!(stats.weekdays.is_empty());
+        assert!(stats.slots.is_empty());
+    }
+
+    #[test]
+    fn single_game() {
+        let solved = [solved("safe", 900), solved("laser", 2100)].join(",");
+        let game = record(12, 19, "escaped", 3000, &solved, Some(""));
+        let stats = Stats::compute(&[game]);
+
+        assert_eq!((stats.games, stats.escaped), (1, 1));
+        assert_eq!(stats.average_escape, Some(Duration::from_secs(3000)));
+        assert_eq!(stats.leaderboard.len(), 1);
+        assert_eq!(stats.leaderboard[0].hints, 1);
+
+        let names: Vec<&str> = stats.puzzles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["laser", "safe"]);
+        let laser = &stats.puzzles[0];
+        assert_eq!((laser.played, laser.solved, laser.hints, laser.blocked), (1, 1, 1, 0));
+        assert_eq!(laser.average_solved_at, Some(Duration::from_secs(2100)));
+        // Taken counts from the previous solve
+        assert_eq!(laser.average_taken, Some(Duration::from_secs(1200)));
+        assert_eq!(stats.most_blocking.as_deref(), Some("laser"));
+
+        assert_eq!(stats.weekdays.len(), 1);
+        assert_eq!(stats.weekdays[0].label, "Mon");
+        assert_eq!(stats.weekdays[0].percent(), 100);
+        assert_eq!(stats.slots.len(), 1);
+        assert_eq!(stats.slots[0].label, "18-21h");
+    }
+
+    #[test]
+    fn escape_rate_by_weekday_and_slot() {
+        let games = [
+            record(12, 19, "escaped", 3000, "", Some("")),
+            record(12, 20, "failed", 3600, "", Some(r#""laser""#)),
+            record(12, 10, "failed", 3600, "", Some(r#""laser","safe""#)),
+            record(14, 19, "escaped", 2500, "", Some("")),
+        ];
+        let stats = Stats::compute(&games);
+
+        let weekdays: Vec<(&str, usize, u64)> =
+            stats.weekdays.iter().map(|r| (r.label.as_str(), r.games, r.percent())).collect();
+        assert_eq!(weekdays, [("Mon", 3, 33), ("Wed", 1, 100)]);
+        let slots: Vec<(&str, usize, u64)> =
+            stats.slots.iter().map(|r| (r.label.as_str(), r.games, r.percent())).collect();
+        assert_eq!(slots, [("09-12h", 1, 0), ("18-21h", 3, 66)]);
+
+        assert_eq!(stats.average_escape, Some(Duration::from_secs(2750)));
+        assert_eq!(stats.leaderboard[0].total, Duration::from_secs(2500));
+        assert_eq!(stats.most_blocking.as_deref(), Some("laser"));
+        let laser = stats.puzzles.iter().find(|p| p.name == "laser").unwrap();
+        assert_eq!((laser.played, laser.blocked), (2, 2));
+    }
+
+    #[test]
+    fn records_without_unsolved() {
+        let games = [
+            record(12, 19, "failed", 3600, &solved("safe", 1200), None),
+            record(13, 19, "escaped", 3000, &solved("safe", 600), None),
+        ];
+        assert!(games.iter().all(|g| g.unsolved.is_empty()));
+
+        let stats = Stats::compute(&games);
+        assert_eq!((stats.games, stats.escaped), (2, 1));
+        let safe = stats.puzzles.iter().find(|p| p.name == "safe").unwrap();
+        assert_eq!((safe.played, safe.solved, safe.blocked), (2, 2, 0));
+        assert_eq!(safe.average_solved_at, Some(Duration::from_secs(900)));
+        // Nothing known to be left unsolved, the longest puzzle is the one to watch
+        assert_eq!(stats.most_blocking.as_deref(), Some("safe"));
+    }
+}
@@ -0,0 +1,472 @@
+use crate::{
+    config::{ServerConfig, SupervisorConfig},
+    logs::{LogLevel, LogSource},
+    App,
+};
+use anyhow::{Context, Result};
+use std::{
+    collections::VecDeque,
+    io::{BufRead, BufReader, Read, Write},
+    process::{Child, ExitStatus, Stdio},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+// --- Server Status ---
+
+pub enum ServerStatus {
+    Starting,
+    Online,
+    Stopping,
+    Stopped(String),
+    Crashed(String),
+    // Crashed, waiting for the backoff delay before the next start
+    Restarting { reason: String, at: Instant },
+}
+
+impl ServerStatus {
+    pub fn label(&self) -> String {
+        match self {
+            ServerStatus::Starting => "STARTING".to_string(),
+            ServerStatus::Online => "ONLINE".to_string(),
+            ServerStatus::Stopping => "STOPPING".to_string(),
+            ServerStatus::Stopped(reason) => format!("STOPPED ({})", reason),
+            ServerStatus::Crashed(reason) => format!("CRASHED ({})", reason),
+            ServerStatus::Restarting { reason, at } => {
+                let secs = at.saturating_duration_since(Instant::now()).as_secs_f32().ceil();
+                format!("CRASHED ({}) - restart in {}s", reason, secs)
+            }
+        }
+    }
+}
+
+// --- Operator Actions ---
+
+#[derive(Clone, Copy)]
+pub enum ServerAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+impl ServerAction {
+    pub fn prompt(&self) -> &'static str {
+        match self {
+            ServerAction::Start => "Start the game server?",
+            ServerAction::Stop => "Stop the game server?",
+            ServerAction::Restart => "Restart the game server?",
+        }
+    }
+}
+
+// An operator stop in progress: SIGTERM was sent, SIGKILL follows at `deadline`
+struct Stopping {
+    deadline: Instant,
+    killed: bool,
+    restart: bool,
+}
+
+// --- Supervisor ---
+
+// Owns the game server process. `tick()` is called from the UI loop and reaps the
+// child without blocking, so a crash shows up within one frame.
+pub struct Supervisor {
+    server: ServerConfig,
+    config: SupervisorConfig,
+    app: Arc<Mutex<App>>,
+    child: Option<Child>,
+    restarts: VecDeque<Instant>,
+    next_restart: Option<Instant>,
+    stopping: Option<Stopping>,
+}
+
+impl Supervisor {
+    pub fn new(server: ServerConfig, config: SupervisorConfig, app: Arc<Mutex<App>>) -> Self {
+        Self {
+            server,
+            config,
+            app,
+            child: None,
+            restarts: VecDeque::new(),
+            next_restart: None,
+            stopping: None,
+        }
+    }
+
+    pub fn start(&mut self) -> Result<()> {
+        let mut command = self.server.command();
+        // Its own process group, so stopping reaches a shell wrapper's children and workers too
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to start server `{}`", self.server.display()))?;
+
+        let stdout = child.stdout.take().context("Failed to capture stdout")?;
+        let stderr = child.stderr.take().context("Failed to capture stderr")?;
+
+        spawn_reader(stdout, self.app.clone(), false);
+        spawn_reader(stderr, self.app.clone(), true);
+
+        {
+            let mut app = self.app.lock().unwrap();
+            app.server_status = ServerStatus::Starting;
+            app.log_event(format!("Started `{}` (pid {})", self.server.display(), child.id()));
+        }
+
+        self.child = Some(child);
+        Ok(())
+    }
+
+    pub fn pid(&self) -> Option<u32> {
+        self.child.as_ref().map(Child::id)
+    }
+
+    pub fn tick(&mut self) {
+        if let Some(child) = &mut self.child {
+            match child.try_wait() {
+                Ok(Some(status)) => {
+                    self.child = None;
+                    self.handle_exit(status);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    self.app.lock().unwrap().log_event(format!("Failed to query server status: {}", e));
+                }
+            }
+        }
+
+        if let Some(stopping) = &mut self.stopping
+            && !stopping.killed
+            && Instant::now() >= stopping.deadline
+            && let Some(child) = &mut self.child
+        {
+            stopping.killed = true;
+            kill(child);
+            self.app.lock().unwrap().log_event(format!(
+                "Server did not exit within {}s, sent SIGKILL",
+                self.config.stop_grace_secs
+            ));
+        }
+
+        if let Some(at) = self.next_restart
+            && Instant::now() >= at
+        {
+            self.next_restart = None;
+            self.app.lock().unwrap().log_event("Restarting server".to_string());
+            if let Err(e) = self.start() {
+                self.handle_failure(format!("{:#}", e));
+            }
+        }
+    }
+
+    pub fn run(&mut self, action: ServerAction) {
+        match action {
+            ServerAction::Start => {
+                if self.child.is_some() {
+                    self.app.lock().unwrap().log_event("Server is already running".to_string());
+                    return;
+                }
+                self.operator_start();
+            }
+            ServerAction::Stop => self.stop(false),
+            ServerAction::Restart => {
+                if self.child.is_some() {
+                    self.stop(true);
+                } else {
+                    self.operator_start();
+                }
+            }
+        }
+    }
+
+    // Writes one line to the server's stdin, e.g. a command typed into the command bar.
+    // Failures are logged, the result tells the caller whether the line went out.
+    pub fn send(&mut self, line: &str) -> bool {
+        let result = match self.child.as_mut().and_then(|c| c.stdin.as_mut()) {
+            Some(stdin) => writeln!(stdin, "{}", line).and_then(|_| stdin.flush()),
+            None => Err(std::io::Error::other("server is not running")),
+        };
+        if let Err(e) = &result {
+            self.app.lock().unwrap().log_marked(
+                LogSource::Supervisor,
+                LogLevel::Error,
+                format!("Failed to send `{}`: {}", line, e),
+            );
+        }
+        result.is_ok()
+    }
+
+    // A manual start also resets the crash history, so the restart limit starts over
+    fn operator_start(&mut self) {
+        self.next_restart = None;
+        self.restarts.clear();
+        self.app.lock().unwrap().log_event("Operator started server".to_string());
+        if let Err(e) = self.start() {
+            self.handle_failure(format!("{:#}", e));
+        }
+    }
+
+    fn stop(&mut self, restart: bool) {
+        let mut app = self.app.lock().unwrap();
+        self.next_restart = None;
+
+        let Some(child) = &mut self.child else {
+            app.log_event("Server is not running".to_string());
+            if matches!(app.server_status, ServerStatus::Restarting { .. }) {
+                app.server_status = ServerStatus::Stopped("stopped by operator".to_string());
+            }
+            return;
+        };
+
+        if let Some(stopping) = &mut self.stopping {
+            // Already stopping, only remember whether to come back up afterwards
+            stopping.restart = restart;
+            return;
+        }
+
+        app.log_event(format!(
+            "Operator {} server, sending SIGTERM (grace period {}s)",
+            if restart { "restarted" } else { "stopped" },
+            self.config.stop_grace_secs
+        ));
+        terminate(child);
+        self.stopping = Some(Stopping {
+            deadline: Instant::now() + Duration::from_secs(self.config.stop_grace_secs),
+            killed: false,
+            restart,
+        });
+        app.server_status = ServerStatus::Stopping;
+    }
+
+    // Called on quit. Blocks for at most the grace period.
+    pub fn shutdown(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            terminate(&mut child);
+            let deadline = Instant::now() + Duration::from_secs(self.config.stop_grace_secs);
+            while Instant::now() < deadline {
+                if let Ok(Some(_)) = child.try_wait() {
+                    return;
+                }
+                thread::sleep(Duration::from_millis(50));
+            }
+            kill(&mut child);
+            let _ = child.wait();
+        }
+    }
+
+    fn handle_exit(&mut self, status: ExitStatus) {
+        let reason = describe_exit(status);
+
+        if let Some(stopping) = self.stopping.take() {
+            {
+                let mut app = self.app.lock().unwrap();
+                app.log_event(format!("Server stopped ({})", reason));
+                app.server_status = ServerStatus::Stopped("stopped by operator".to_string());
+            }
+            if stopping.restart {
+                self.operator_start();
+            }
+            return;
+        }
+
+        if status.success() {
+            let mut app = self.app.lock().unwrap();
+            app.log_event(format!("Server exited ({})", reason));
+            app.server_status = ServerStatus::Stopped(reason);
+        } else {
+            self.app.lock().unwrap().log_marked(
+                LogSource::Supervisor,
+                LogLevel::Error,
+                format!("Server crashed ({})", reason),
+            );
+            self.handle_failure(reason);
+        }
+    }
+
+    // Decides whether and when to restart after a crash or a failed start
+    fn handle_failure(&mut self, reason: String) {
+        let mut app = self.app.lock().unwrap();
+
+        if !self.config.auto_restart {
+            app.server_status = ServerStatus::Crashed(reason);
+            return;
+        }
+
+        let now = Instant::now();
+        let Some(delay) = restart_delay(&self.config, &mut self.restarts, now) else {
+            app.log_event(format!(
+                "Restart limit reached ({} restarts in {}s), giving up",
+                self.restarts.len(),
+                self.config.restart_window_secs
+            ));
+            app.server_status = ServerStatus::Crashed(reason);
+            return;
+        };
+        let at = now + Duration::from_secs(delay);
+
+        self.restarts.push_back(now);
+        self.next_restart = Some(at);
+
+        app.log_event(format!(
+            "Restart {}/{} scheduled in {}s",
+            self.restarts.len(),
+            self.config.max_restarts,
+            delay
+        ));
+        app.server_status = ServerStatus::Restarting { reason, at };
+    }
+}
+
+// Seconds to wait before the next restart, `None` once the limit is reached.
+// Restarts older than the window are dropped first, so a server that ran
+// stable for a while starts over at the initial delay.
+fn restart_delay(config: &SupervisorConfig, restarts: &mut VecDeque<Instant>, now: Instant) -> Option<u64> {
+    let window = Duration::from_secs(config.restart_window_secs);
+    while restarts.front().is_some_and(|t| now.duration_since(*t) > window) {
+        restarts.pop_front();
+    }
+    if restarts.len() >= config.max_restarts as usize {
+        return None;
+    }
+
+    // Exponential backoff based on how many restarts happened in the current window
+    let exponent = restarts.len().min(16) as u32;
+    Some(config.backoff_initial_secs.saturating_mul(1 << exponent).min(config.backoff_max_secs))
+}
+
+// The server leads its own process group (see `start`), the signals go to the whole group
+#[cfg(unix)]
+fn signal_group(child: &Child, signal: libc::c_int) {
+    // SAFETY: plain kill(2) on the group of a child we have not reaped yet
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), signal);
+    }
+}
+
+#[cfg(unix)]
+fn terminate(child: &mut Child) {
+    signal_group(child, libc::SIGTERM);
+}
+
+#[cfg(unix)]
+fn kill(child: &mut Child) {
+    signal_group(child, libc::SIGKILL);
+}
+
+#[cfg(not(unix))]
+fn terminate(child: &mut Child) {
+    let _ = child.kill();
+}
+
+#[cfg(not(unix))]
+fn kill(child: &mut Child) {
+    let _ = child.kill();
+}
+
+fn spawn_reader(stream: impl Read + Send + 'static, app: Arc<Mutex<App>>, is_stderr: bool) {
+    thread::spawn(move || {
+        for l in BufReader::new(stream).lines().map_while(Result::ok) {
+            let mut app = app.lock().unwrap();
+            app.process_log(l, is_stderr);
+        }
+    });
+}
+
+#[cfg(unix)]
+fn describe_exit(status: ExitStatus) -> String {
+    use std::os::unix::process::ExitStatusExt;
+
+    match (status.code(), status.signal()) {
+        (Some(code), _) => format!("exit code {}", code),
+        (None, Some(signal)) => format!("signal {} ({})", signal, signal_name(signal)),
+        _ => status.to_string(),
+    }
+}
+
+#[cfg(not(unix))]
+fn describe_exit(status: ExitStatus) -> String {
+    match status.code() {
+        Some(code) => format!("exit code {}", code),
+        None => status.to_string(),
+    }
+}
+
+#[cfg(unix)]
+fn signal_name(signal: i32) -> &'static str {
+    match signal {
+        1 => "SIGHUP",
+        2 => "SIGINT",
+        3 => "SIGQUIT",
+        6 => "SIGABRT",
+        9 => "SIGKILL",
+        11 => "SIGSEGV",
+        13 => "SIGPIPE",
+        15 => "SIGTERM",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> SupervisorConfig {
+        SupervisorConfig {
+            auto_restart: true,
+            backoff_initial_secs: 1,
+            backoff_max_secs: 10,
+            max_restarts: 10,
+            ..SupervisorConfig::default()
+        }
+    }
+
+    // Runs `count` crashes one second apart, returning the delay of each
+    fn delays(config: &SupervisorConfig, restarts: &mut VecDeque<Instant>, start: Instant, count: u64) -> Vec<Option<u64>> {
+        (0..count)
+            .map(|i| {
+                let now = start + Duration::from_secs(i);
+                let delay = restart_delay(config, restarts, now);
+                if delay.is_some() {
+                    restarts.push_back(now);
+                }
+                delay
+            })
+            .collect()
+    }
+
+    #[test]
+    fn delay_doubles_up_to_the_cap() {
+        let mut restarts = VecDeque::new();
+        let delays = delays(&config(), &mut restarts, Instant::now(), 6);
+        assert_eq!(delays, [Some(1), Some(2), Some(4), Some(8), Some(10), Some(10)]);
+    }
+
+    #[test]
+    fn gives_up_at_the_restart_limit() {
+        let config = SupervisorConfig { max_restarts: 3, ..config() };
+        let mut restarts = VecDeque::new();
+        let delays = delays(&config, &mut restarts, Instant::now(), 5);
+        assert_eq!(delays, [Some(1), Some(2), Some(4), None, None]);
+    }
+
+    #[test]
+    fn stable_run_resets_the_backoff() {
+        let config = config();
+        let start = Instant::now();
+        let mut restarts = VecDeque::new();
+        delays(&config, &mut restarts, start, 4);
+
+        // Still inside the window the backoff keeps growing
+        assert_eq!(restart_delay(&config, &mut restarts, start + Duration::from_secs(300)), Some(10));
+
+        // After a full window without crashes it starts over
+        let later = start + Duration::from_secs(config.restart_window_secs + 10);
+        assert_eq!(restart_delay(&config, &mut restarts, later), Some(1));
+        assert!(restarts.is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// --- Tabs ---
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tab {
+    #[default]
+    Overview,
+    Puzzles,
+    Clients,
+    Logs,
+    System,
+    Game,
+}
+
+impl Tab {
+    // In the order of the tab bar and their number keys
+    pub const ALL: [Tab; 6] = [Tab::Overview, Tab::Puzzles, Tab::Clients, Tab::Logs, Tab::System, Tab::Game];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Tab::Overview => "Overview",
+            Tab::Puzzles => "Puzzles",
+            Tab::Clients => "Clients",
+            Tab::Logs => "Logs",
+            Tab::System => "System",
+            Tab::Game => "Game",
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        Self::ALL.iter().position(|t| t == self).unwrap_or(0)
+    }
+
+    // '1' is the first tab
+    pub fn from_key(c: char) -> Option<Tab> {
+        let index = c.to_digit(10)?.checked_sub(1)?;
+        Self::ALL.get(index as usize).copied()
+    }
+
+    pub fn next(&self) -> Tab {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(&self) -> Tab {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+// --- UI State ---
+//
+// Remembered between runs in a small JSON file, e.g. {"tab":"logs"}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    pub tab: Tab,
+}
+
+impl UiState {
+    // A missing or unreadable file just starts on the overview
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let text = serde_json::to_string(self)?;
+        std::fs::write(path, text).with_context(|| format!("Failed to save UI state {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_keys_pick_the_tab() {
+        assert_eq!(Tab::from_key('1'), Some(Tab::Overview));
+        assert_eq!(Tab::from_key('6'), Some(Tab::Game));
+        assert_eq!(Tab::from_key('0'), None);
+        assert_eq!(Tab::from_key('7'), None);
+        assert_eq!(Tab::from_key('x'), None);
+    }
+
+    #[test]
+    fn next_and_previous_wrap_around() {
+        assert_eq!(Tab::Overview.next(), Tab::Puzzles);
+        assert_eq!(Tab::Game.next(), Tab::Overview);
+        assert_eq!(Tab::Overview.previous(), Tab::Game);
+        for tab in Tab::ALL {
+            assert_eq!(tab.next().previous(), tab);
+        }
+    }
+
+    #[test]
+    fn state_is_saved_and_loaded() {
+        let path = std::env::temp_dir().join(format!("servertui-test-{}-state.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(UiState::load(&path).tab, Tab::Overview);
+
+        UiState { tab: Tab::Logs }.save(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), r#"{"tab":"logs"}"#);
+        assert_eq!(UiState::load(&path).tab, Tab::Logs);
+
+        // Anything unreadable starts on the overview again
+        std::fs::write(&path, r#"{"tab":"nope"}"#).unwrap();
+        assert_eq!(UiState::load(&path).tab, Tab::Overview);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
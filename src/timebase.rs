@@ -0,0 +1,32 @@
+use chrono::{DateTime, Local};
+use std::{sync::Mutex, time::Instant};
+#[cfg(test)]
+use std::sync::MutexGuard;
+
+// --- Time Source ---
+//
+// Live runs use the system clock. A replay drives time itself, so the game clock,
+// puzzle and client ages and log timestamps follow the recording instead.
+
+static REPLAY_TIME: Mutex<Option<(Instant, DateTime<Local>)>> = Mutex::new(None);
+
+pub fn now() -> Instant {
+    REPLAY_TIME.lock().unwrap().map_or_else(Instant::now, |(instant, _)| instant)
+}
+
+pub fn local_now() -> DateTime<Local> {
+    REPLAY_TIME.lock().unwrap().map_or_else(Local::now, |(_, local)| local)
+}
+
+pub fn set(instant: Instant, local: DateTime<Local>) {
+    *REPLAY_TIME.lock().unwrap() = Some((instant, local));
+}
+
+// Tests sharing the replay time take this lock, it starts them on the system clock
+#[cfg(test)]
+pub fn test_lock() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    let guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    *REPLAY_TIME.lock().unwrap() = None;
+    guard
+}